@@ -0,0 +1,236 @@
+//! The activity log
+//!
+//! Todoist records who added, updated, completed or deleted each item, note and project.
+//! Use `Client::activity` to read that history, a page at a time.
+use types::*;
+
+use serde::{Deserialize, Deserializer};
+
+use std::fmt;
+
+/// The kind of object an event happened to
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectType {
+    Item,
+    Note,
+    Project,
+
+    /// an object this library doesn't know about
+    Unknown,
+}
+
+/// What happened to the object
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum EventType {
+    Added,
+    Updated,
+    Deleted,
+    Completed,
+    Uncompleted,
+    Archived,
+    Unarchived,
+    Shared,
+    Left,
+
+    /// an event this library doesn't know about
+    Unknown,
+}
+
+const OBJECT_TYPES : [ObjectType; 3] = [ObjectType::Item, ObjectType::Note, ObjectType::Project];
+
+const EVENT_TYPES : [EventType; 9] = [
+    EventType::Added, EventType::Updated, EventType::Deleted,
+    EventType::Completed, EventType::Uncompleted, EventType::Archived,
+    EventType::Unarchived, EventType::Shared, EventType::Left,
+];
+
+/// A single entry in the activity log
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ActivityEvent {
+    /// The event's unique ID
+    pub id : ID,
+
+    pub object_type : ObjectType,
+
+    /// The ID of the item, note or project the event happened to
+    pub object_id : ID,
+
+    pub event_type : EventType,
+
+    /// when the event happened
+    pub event_date : Date,
+
+    /// The project the object belongs to
+    pub parent_project_id : Option<ID>,
+
+    /// The item the object belongs to, only set for notes
+    pub parent_item_id : Option<ID>,
+
+    /// The ID of the user who caused the event, none if it was the current user
+    pub initiator_id : Option<ID>,
+
+    #[serde(default)]
+    pub extra_data : ActivityExtra,
+}
+
+/// Details about an event, which fields are set depends on the object and event types
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct ActivityExtra {
+    /// The item's or note's text
+    pub content : Option<String>,
+
+    /// The item's text before it was updated
+    pub last_content : Option<String>,
+
+    /// The project's name
+    pub name : Option<String>,
+
+    /// The project's name before it was updated
+    pub last_name : Option<String>,
+
+    /// The item's due date
+    pub due_date : Option<Date>,
+
+    /// The item's due date before it was updated
+    pub last_due_date : Option<Date>,
+
+    /// The user the item is assigned to
+    pub responsible_uid : Option<ID>,
+
+    /// The user the item was assigned to before it was updated
+    pub last_responsible_uid : Option<ID>,
+
+    /// The client the change was made from
+    pub client : Option<String>,
+}
+
+/// Filters for a page of activity log events, Todoist allows up to 100 events in a page
+pub type ActivityFilter = Paged<ActivityEventFilter>;
+
+/// The filters specific to activity log events, see `ActivityFilter`
+#[derive(Debug, Clone, Default)]
+pub struct ActivityEventFilter {
+    /// only get events for this kind of object
+    pub object_type : Option<ObjectType>,
+
+    /// only get events for this object, `object_type` must also be set
+    pub object_id : Option<ID>,
+
+    /// only get this kind of event
+    pub event_type : Option<EventType>,
+
+    /// only get events for objects in this project
+    pub parent_project_id : Option<ID>,
+
+    /// only get events for notes on this item
+    pub parent_item_id : Option<ID>,
+
+    /// only get events caused by this user
+    pub initiator_id : Option<ID>,
+}
+
+impl Paged<ActivityEventFilter> {
+    pub fn object_type(mut self, typ : ObjectType) -> Self {
+        self.filter.object_type = Some(typ);
+        self
+    }
+
+    pub fn object_id(mut self, id : ID) -> Self {
+        self.filter.object_id = Some(id);
+        self
+    }
+
+    pub fn event_type(mut self, typ : EventType) -> Self {
+        self.filter.event_type = Some(typ);
+        self
+    }
+
+    pub fn parent_project_id(mut self, id : ID) -> Self {
+        self.filter.parent_project_id = Some(id);
+        self
+    }
+
+    pub fn parent_item_id(mut self, id : ID) -> Self {
+        self.filter.parent_item_id = Some(id);
+        self
+    }
+
+    pub fn initiator_id(mut self, id : ID) -> Self {
+        self.filter.initiator_id = Some(id);
+        self
+    }
+}
+
+impl PageFilter for ActivityEventFilter {
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(typ) = self.object_type {
+            params.push(("object_type", typ.to_string()));
+        }
+        if let Some(id) = self.object_id {
+            params.push(("object_id", id.to_string()));
+        }
+        if let Some(typ) = self.event_type {
+            params.push(("event_type", typ.to_string()));
+        }
+        if let Some(id) = self.parent_project_id {
+            params.push(("parent_project_id", id.to_string()));
+        }
+        if let Some(id) = self.parent_item_id {
+            params.push(("parent_item_id", id.to_string()));
+        }
+        if let Some(id) = self.initiator_id {
+            params.push(("initiator_id", id.to_string()));
+        }
+        params
+    }
+}
+
+impl fmt::Display for ObjectType {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ObjectType::Item => "item",
+            ObjectType::Note => "note",
+            ObjectType::Project => "project",
+            ObjectType::Unknown => "unknown",
+        })
+    }
+}
+
+impl fmt::Display for EventType {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            EventType::Added => "added",
+            EventType::Updated => "updated",
+            EventType::Deleted => "deleted",
+            EventType::Completed => "completed",
+            EventType::Uncompleted => "uncompleted",
+            EventType::Archived => "archived",
+            EventType::Unarchived => "unarchived",
+            EventType::Shared => "shared",
+            EventType::Left => "left",
+            EventType::Unknown => "unknown",
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ObjectType {
+    fn deserialize<D : Deserializer<'de>>(deserializer : D) -> Result<ObjectType, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(OBJECT_TYPES.iter().cloned()
+            .find(|t| t.to_string() == s)
+            .unwrap_or(ObjectType::Unknown))
+    }
+}
+
+impl<'de> Deserialize<'de> for EventType {
+    fn deserialize<D : Deserializer<'de>>(deserializer : D) -> Result<EventType, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(EVENT_TYPES.iter().cloned()
+            .find(|t| t.to_string() == s)
+            .unwrap_or(EventType::Unknown))
+    }
+}
@@ -0,0 +1,175 @@
+//! Backups
+//!
+//! Todoist keeps a daily backup of each user's projects, see `Client::backups` and `Client::download_backup`.
+//! A backup is a zip archive with a template for each project, named after the project and its ID
+//! (e.g. `Work [2203306141].csv`). Once the archive has been extracted, `BackupContents::read_dir` reads
+//! those templates, and `BackupContents::restore_plan` finds the projects and items that have been deleted since.
+use types::*;
+use cache::Cache;
+use template::{Template, TemplateRow};
+use completed::CompletedItem;
+use resource::Project;
+use Transaction;
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// A backup kept by Todoist
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Backup {
+    /// when the backup was made, e.g. "2018-10-19 02:03"
+    pub version : String,
+
+    /// where the backup's zip archive can be downloaded from
+    pub url : String,
+}
+
+/// A project in a backup
+#[derive(Debug, Clone)]
+pub struct BackupProject {
+    /// The project's ID, if it's in the template's file name
+    pub id : Option<ID>,
+
+    pub name : String,
+
+    /// The project's items and notes
+    pub template : Template,
+}
+
+/// The projects in an extracted backup
+#[derive(Debug, Clone, Default)]
+pub struct BackupContents {
+    pub projects : Vec<BackupProject>,
+}
+
+/// The commands needed to bring back projects and items that are in a backup, but no longer in the cache
+#[derive(Debug, Clone, Default)]
+pub struct RestorePlan {
+    /// Projects that have been deleted, these are created again with all their items
+    pub projects : Vec<BackupProject>,
+
+    /// Items that have been deleted from projects that still exist, and the ID of their project,
+    /// this is empty unless the plan was made with the completed items
+    pub items : Vec<(ID, Template)>,
+}
+
+impl BackupContents {
+    /// Read the templates in an extracted backup
+    pub fn read_dir<P : AsRef<Path>>(path : P) -> Result<BackupContents, Error> {
+        let mut contents = BackupContents::default();
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+            if path.extension().map(|ext| ext != "csv").unwrap_or(true) {
+                continue;
+            }
+
+            let stem = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let (name, id) = split_file_name(&stem);
+            contents.projects.push(BackupProject {
+                id: id,
+                name: name,
+                template: Template::parse(&fs::read_to_string(&path)?)?,
+            });
+        }
+        contents.projects.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(contents)
+    }
+
+    /// Compare the backup to the cache, and plan how to restore what's been deleted
+    ///
+    /// Projects are matched by ID, or by name if the ID isn't known. Archived projects aren't part of a sync,
+    /// call `Cache::sync_archived` first so they aren't restored as duplicates.
+    ///
+    /// Completed items aren't part of a sync either, so items are only restored when `completed` is given:
+    /// the items completed since the backup was made, see `Client::completed_items`.
+    /// Without it only deleted projects are restored. Items are matched by their content,
+    /// and each open or completed item matches a single task in the backup.
+    pub fn restore_plan(&self, cache : &Cache, completed : Option<&[CompletedItem]>) -> RestorePlan {
+        let mut plan = RestorePlan::default();
+        for project in self.projects.iter() {
+            let matches = |p : &&Project| !p.is_deleted && match project.id {
+                Some(id) => p.id == id,
+                None => p.name == project.name,
+            };
+            if cache.archived_projects.values().any(|p| matches(&p)) {
+                continue;
+            }
+
+            let existing = match cache.projects.values().find(matches) {
+                Some(v) => v,
+                None => {
+                    plan.projects.push(project.clone());
+                    continue;
+                },
+            };
+
+            let completed = match completed {
+                Some(v) => v,
+                None => continue,
+            };
+
+            // the number of items with each content that are still in the project
+            let mut remaining : HashMap<String, usize> = HashMap::new();
+            let open = cache.items.values().filter(|item| !item.is_deleted);
+            let done = completed.iter().map(|c| &c.item);
+            for item in open.chain(done).filter(|item| item.project_id == existing.id) {
+                if let Some(ref content) = item.content {
+                    *remaining.entry(content.trim().to_string()).or_insert(0) += 1;
+                }
+            }
+
+            // keep each missing task, along with the notes after it
+            let mut missing = Template::default();
+            let mut keep = false;
+            for row in project.template.rows.iter() {
+                if let TemplateRow::Task(ref task) = row {
+                    keep = match remaining.get_mut(task.content.trim()) {
+                        Some(ref mut n) if **n > 0 => {
+                            **n -= 1;
+                            false
+                        },
+                        _ => true,
+                    };
+                }
+                if keep {
+                    missing.rows.push(row.clone());
+                }
+            }
+
+            if !missing.rows.is_empty() {
+                plan.items.push((existing.id, missing));
+            }
+        }
+        plan
+    }
+}
+
+impl RestorePlan {
+    /// True if nothing needs to be restored
+    pub fn is_empty(&self) -> bool {
+        self.projects.is_empty() && self.items.is_empty()
+    }
+
+    /// Add the commands to restore everything in this plan to a transaction
+    pub fn apply(&self, tx : &mut Transaction, cache : &Cache) {
+        for project in self.projects.iter() {
+            project.template.import_new(tx, cache, &project.name);
+        }
+        for &(project_id, ref template) in self.items.iter() {
+            template.import(tx, cache, project_id);
+        }
+    }
+}
+
+/// Split a template's file name, e.g. "Work [2203306141]", into the project's name and ID
+fn split_file_name(stem : &str) -> (String, Option<ID>) {
+    if stem.ends_with(']') {
+        if let Some(start) = stem.rfind('[') {
+            if let Ok(id) = stem[start + 1..stem.len() - 1].parse() {
+                return (stem[..start].trim().to_string(), Some(id));
+            }
+        }
+    }
+    (stem.to_string(), None)
+}
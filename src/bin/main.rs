@@ -4,6 +4,7 @@ extern crate todoist;
 extern crate clap;
 extern crate serde_json;
 extern crate serde;
+extern crate chrono;
 
 
 use std::io::{self, BufRead, Write};
@@ -13,6 +14,9 @@ use std::fs;
 use std::fmt;
 
 use clap::{App, Arg, SubCommand};
+use chrono::TimeZone;
+use todoist::quick_add::QuickAdd;
+use todoist::template::Template;
 /// Ask the user a question.
 ///
 /// generally you should use the `query!` macro over this function.
@@ -85,7 +89,7 @@ fn main() {
                                 .arg(Arg::with_name("color")
                                     .short("c")
                                     .long("color")
-                                    .help("the project's color.")
+                                    .help("the project's color, as a name, number or hex code")
                                     .value_name("COLOR")
                                     .default_value("Light Grey")
                                     .takes_value(true))
@@ -95,16 +99,15 @@ fn main() {
                                     .help("make this project a favorite")))
                             .subcommand(SubCommand::with_name("item")
                                 .arg(Arg::with_name("content")
-                                    .help("the item's text")
+                                    .help("the item's text, e.g. \"Buy milk tomorrow #Errands @store p2\"")
                                     .value_name("NAME")
                                     .required(true)
                                     .takes_value(true))
                                 .arg(Arg::with_name("project")
                                     .short("p")
                                     .long("project")
-                                    .help("set the item's parent project")
+                                    .help("set the item's parent project, defaults to the inbox")
                                     .value_name("STRING")
-                                    .default_value("Inbox")
                                     .takes_value(true))
                                 .arg(Arg::with_name("due")
                                     .short("d")
@@ -114,12 +117,12 @@ fn main() {
                                     .takes_value(true))
                                 .arg(Arg::with_name("priority")
                                     .long("priority")
-                                    .help("set the item's priority, a number from 1-4")
-                                    .value_name("NUMBER")
-                                    .default_value("1")
+                                    .help("set the item's priority, from p1 (urgent) to p4 (normal)")
+                                    .value_name("PRIORITY")
                                     .takes_value(true))
                                 .arg(Arg::with_name("label")
-                                    .long("l")
+                                    .short("l")
+                                    .long("label")
                                     .help("add a label to the item")
                                     .value_name("NAME")
                                     .multiple(true)
@@ -127,7 +130,105 @@ fn main() {
                                 .arg(Arg::with_name("favorite")
                                     .short("f")
                                     .long("favorite")
-                                    .help("make this item a favorite"))))
+                                    .help("make this item a favorite"))
+                                .arg(Arg::with_name("raw")
+                                    .short("r")
+                                    .long("raw")
+                                    .help("don't parse #project, @label, priority or due date from the item's text"))))
+                        .subcommand(SubCommand::with_name("attach")
+                            .about("Attach a local file to an item")
+                            .arg(Arg::with_name("item")
+                                .help("the item's ID")
+                                .value_name("ID")
+                                .required(true)
+                                .takes_value(true))
+                            .arg(Arg::with_name("file")
+                                .help("the file to upload")
+                                .value_name("FILE")
+                                .required(true)
+                                .takes_value(true))
+                            .arg(Arg::with_name("message")
+                                .short("m")
+                                .long("message")
+                                .help("the text of the note the file is attached to")
+                                .value_name("STRING")
+                                .default_value("")
+                                .takes_value(true)))
+                        .subcommand(SubCommand::with_name("log")
+                            .about("Show the history of your account")
+                            .subcommand(SubCommand::with_name("completed")
+                                .about("list completed items, most recent first")
+                                .arg(Arg::with_name("since")
+                                    .short("s")
+                                    .long("since")
+                                    .help("only list items completed since this date, e.g. \"yesterday\" or \"oct 1\"")
+                                    .value_name("DATE")
+                                    .takes_value(true))
+                                .arg(Arg::with_name("project")
+                                    .short("p")
+                                    .long("project")
+                                    .help("only list items in this project")
+                                    .value_name("STRING")
+                                    .takes_value(true))))
+                        .subcommand(SubCommand::with_name("stats")
+                            .about("Show your productivity stats")
+                            .arg(Arg::with_name("json")
+                                .long("json")
+                                .help("print the stats as JSON")))
+                        .subcommand(SubCommand::with_name("template")
+                            .about("Export or import project templates")
+                            .subcommand(SubCommand::with_name("export")
+                                .about("write a project's items and notes to a CSV template")
+                                .arg(Arg::with_name("project")
+                                    .help("the project to export")
+                                    .value_name("PROJECT")
+                                    .required(true)
+                                    .takes_value(true))
+                                .arg(Arg::with_name("output")
+                                    .short("o")
+                                    .long("output")
+                                    .help("the file to write the template to, instead of stdout")
+                                    .value_name("FILE")
+                                    .takes_value(true)))
+                            .subcommand(SubCommand::with_name("import")
+                                .about("add the items and notes in a CSV template to a project")
+                                .arg(Arg::with_name("file")
+                                    .help("the template to import")
+                                    .value_name("FILE")
+                                    .required(true)
+                                    .takes_value(true))
+                                .arg(Arg::with_name("project")
+                                    .short("p")
+                                    .long("project")
+                                    .help("import into an existing project")
+                                    .value_name("PROJECT")
+                                    .takes_value(true))
+                                .arg(Arg::with_name("new")
+                                    .short("n")
+                                    .long("new")
+                                    .help("import into a new project with this name")
+                                    .value_name("NAME")
+                                    .conflicts_with("project")
+                                    .required_unless("project")
+                                    .takes_value(true))))
+                        .subcommand(SubCommand::with_name("archived")
+                            .about("Browse archived projects")
+                            .subcommand(SubCommand::with_name("list")
+                                .about("list archived projects"))
+                            .subcommand(SubCommand::with_name("items")
+                                .about("list the items in an archived project")
+                                .arg(Arg::with_name("project")
+                                    .help("the archived project's name")
+                                    .value_name("NAME")
+                                    .required(true)
+                                    .takes_value(true)))
+                            .subcommand(SubCommand::with_name("unarchive")
+                                .about("restore an archived project")
+                                .arg(Arg::with_name("project")
+                                    .help("the archived project's name")
+                                    .value_name("NAME")
+                                    .required(true)
+                                    .takes_value(true))))
                         .get_matches();
 
     let mut cache : todoist::Cache = read_cache("todoist.rs").unwrap();
@@ -154,16 +255,250 @@ fn main() {
                     .name(name)
                     .indent(parent.indent + 1)
                     .item_order(parent.item_order)
-                    .is_favorite(matches.is_present("favorite") as isize)
+                    .is_favorite(matches.is_present("favorite"))
                     .color(matches.value_of("color").unwrap().parse().unwrap()));
         } else if let Some(matches) = matches.subcommand_matches("item") {
-            let parent = cache.get_project(matches.value_of("project").unwrap()).unwrap();
+            let content = matches.value_of("content").unwrap();
+            let mut quick = if matches.is_present("raw") {
+                QuickAdd {
+                    content: content.to_string(),
+                    project: None,
+                    labels: Vec::new(),
+                    priority: None,
+                    date_string: None,
+                }
+            } else {
+                QuickAdd::parse(content, &cache.user.date_parser())
+            };
+
+            if let Some(project) = matches.value_of("project") {
+                quick.project = Some(project.to_string());
+            }
+            if let Some(priority) = matches.value_of("priority") {
+                quick.priority = Some(priority.parse().unwrap());
+            }
+            if let Some(due) = matches.value_of("due") {
+                quick.date_string = Some(due.to_string());
+            }
+            if let Some(labels) = matches.values_of("label") {
+                quick.labels.extend(labels.map(|l| l.to_string()));
+            }
+
+            if let Some(ref due) = quick.date_string {
+                match cache.user.date_parser().parse(due) {
+                    Ok(date) => println!("due {}", date.to_timezone(&cache.user.tz_info.offset())),
+                    Err(e) => println!("{}, leaving it for Todoist to parse", e),
+                }
+            }
+
+            match quick.command(&cache) {
+                Ok(item) => tx.exec(item),
+                Err(e) => {
+                    println!("{}", e);
+                    return;
+                },
+            };
+        }
+
+        if let Err(e) = tx.validate(&cache.user) {
+            println!("{}", e);
+            return;
+        }
+        tx.commit().unwrap();
+    }
 
-            tx.exec(todoist::Item::add()
-                    .content(matches.value_of("content").unwrap().to_string())
-                    .project_id(parent.id)
-                    .priority(matches.value_of("priority").unwrap().parse().unwrap()));
+    if let Some(matches) = matches.subcommand_matches("attach") {
+        let item_id : todoist::ID = matches.value_of("item").unwrap().parse().unwrap();
+        if !cache.items.contains_key(&item_id) {
+            println!("no item with ID {}", item_id);
+            return;
         }
+
+        let attachment = client.upload_with_progress(matches.value_of("file").unwrap(), |sent, total| {
+            print!("\ruploading... {}/{} bytes", sent, total);
+            io::stdout().flush().unwrap();
+        }).unwrap();
+        println!();
+
+        let mut tx = client.begin();
+        tx.exec(todoist::Note::add()
+                .item_id(item_id)
+                .content(matches.value_of("message").unwrap().to_string())
+                .file_attachment(attachment));
         tx.commit().unwrap();
     }
-}
\ No newline at end of file
+
+    if let Some(matches) = matches.subcommand_matches("log") {
+        if let Some(matches) = matches.subcommand_matches("completed") {
+            let mut filter = todoist::completed::CompletedFilter::new().limit(50);
+
+            if let Some(since) = matches.value_of("since") {
+                let date = match cache.user.date_parser().parse(since) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        println!("{}", e);
+                        return;
+                    },
+                };
+                filter = filter.since(if date.all_day {
+                    cache.user.tz_info.offset().from_local_datetime(&date.date().and_hms(0, 0, 0)).unwrap()
+                } else {
+                    date.timestamp
+                });
+            }
+
+            if let Some(path) = matches.value_of("project") {
+                match cache.get_project(path) {
+                    Some(project) => filter = filter.project_id(project.id),
+                    None => {
+                        println!("no project named \"{}\"", path);
+                        return;
+                    },
+                }
+            }
+
+            loop {
+                let page = client.completed_items(&filter).unwrap();
+                for completed in page.items.iter() {
+                    let project = page.projects.get(&completed.item.project_id)
+                        .map(|p| p.name.as_str())
+                        .unwrap_or("");
+                    println!("{}  {}  #{}",
+                             completed.completed.to_timezone(&cache.user.tz_info.offset()),
+                             completed.item.content.as_ref().map(|c| c.as_str()).unwrap_or(""),
+                             project);
+                }
+
+                if page.items.len() < filter.limit {
+                    break;
+                }
+                filter = filter.next_page();
+            }
+        }
+    }
+
+    if let Some(matches) = matches.subcommand_matches("stats") {
+        let stats = client.productivity_stats().unwrap();
+        if matches.is_present("json") {
+            println!("{}", serde_json::to_string_pretty(&stats).unwrap());
+            return;
+        }
+
+        let goals = &stats.goals;
+        println!("karma: {} ({})", stats.karma, stats.karma_trend);
+        println!("completed: {} total, {} today", stats.completed_count, cache.user.completed_today);
+        println!("daily goal: {} items, streak {} days (best {})",
+                 goals.daily_goal, goals.current_daily_streak.count, goals.max_daily_streak.count);
+        println!("weekly goal: {} items, streak {} weeks (best {})",
+                 goals.weekly_goal, goals.current_weekly_streak.count, goals.max_weekly_streak.count);
+
+        println!("\ndays:");
+        for day in stats.days_items.iter() {
+            println!("  {}  {}", day.date.format("%a %d %b"), day.total_completed);
+        }
+
+        println!("\nweeks:");
+        for week in stats.week_items.iter() {
+            println!("  {} - {}  {}", week.week.start.format("%d %b"), week.week.end.format("%d %b"), week.total_completed);
+        }
+
+        println!("\nkarma updates:");
+        for update in stats.karma_update_reasons.iter() {
+            let reasons : Vec<String> = update.positive_karma_reasons.iter()
+                .chain(update.negative_karma_reasons.iter())
+                .map(|r| r.to_string())
+                .collect();
+            println!("  {}  +{} -{}  {}", update.time.to_timezone(&cache.user.tz_info.offset()),
+                     update.positive_karma, update.negative_karma, reasons.join(", "));
+        }
+    }
+
+    if let Some(matches) = matches.subcommand_matches("template") {
+        if let Some(matches) = matches.subcommand_matches("export") {
+            let path = matches.value_of("project").unwrap();
+            let project = match cache.get_project(path) {
+                Some(v) => v,
+                None => {
+                    println!("no project named \"{}\"", path);
+                    return;
+                },
+            };
+
+            let csv = Template::from_project(&cache, project).to_csv();
+            match matches.value_of("output") {
+                Some(file) => fs::write(file, csv).unwrap(),
+                None => print!("{}", csv),
+            }
+        } else if let Some(matches) = matches.subcommand_matches("import") {
+            let template = match Template::parse(&fs::read_to_string(matches.value_of("file").unwrap()).unwrap()) {
+                Ok(v) => v,
+                Err(e) => {
+                    println!("{}", e);
+                    return;
+                },
+            };
+
+            let mut tx = client.begin();
+            if let Some(name) = matches.value_of("new") {
+                template.import_new(&mut tx, &cache, name);
+            } else {
+                let path = matches.value_of("project").unwrap();
+                match cache.get_project(path) {
+                    Some(project) => template.import(&mut tx, &cache, project.id),
+                    None => {
+                        println!("no project named \"{}\"", path);
+                        return;
+                    },
+                }
+            }
+
+            if let Err(e) = tx.validate(&cache.user) {
+                println!("{}", e);
+                return;
+            }
+            tx.commit().unwrap();
+        }
+    }
+
+    if let Some(matches) = matches.subcommand_matches("archived") {
+        cache.sync_archived(&client).unwrap();
+        write_cache("todoist.rs", &cache).unwrap();
+
+        if matches.subcommand_matches("list").is_some() {
+            let mut projects : Vec<&todoist::Project> = cache.archived_projects.values().collect();
+            projects.sort_by(|a, b| a.name.cmp(&b.name));
+            for project in projects {
+                println!("{}", project.name);
+            }
+            return;
+        }
+
+        let (name, unarchive) = match matches.subcommand() {
+            ("items", Some(matches)) => (matches.value_of("project").unwrap(), false),
+            ("unarchive", Some(matches)) => (matches.value_of("project").unwrap(), true),
+            _ => return,
+        };
+        let project = match cache.get_archived_project(name) {
+            Some(v) => v,
+            None => {
+                println!("no archived project named \"{}\"", name);
+                return;
+            },
+        };
+
+        if unarchive {
+            let mut tx = client.begin();
+            tx.exec(project.unarchive());
+            tx.commit().unwrap();
+        } else {
+            let mut items = client.get_project(project.id).unwrap().items;
+            items.sort_by_key(|item| item.item_order);
+            for item in items.iter() {
+                println!("{}{}{}",
+                         "  ".repeat(item.indent.max(1) as usize - 1),
+                         if item.checked { "[x] " } else { "[ ] " },
+                         item.content.as_ref().map(|c| c.as_str()).unwrap_or(""));
+            }
+        }
+    }
+}
@@ -1,7 +1,8 @@
-use super::{Client, Collaborator, Project, Item, Label, User, ID, ResourceType, Transaction};
+use super::{Client, ItemData, ProjectData, Collaborator, Project, Item, Label, LiveNotification, Location, Note, NotificationSetting, ProjectNote, User, ID, ResourceType, Transaction};
 use super::types::Error;
+use super::query::{Query, Context};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 #[derive(Serialize, Deserialize, Default)]
@@ -16,8 +17,25 @@ pub struct Cache {
     pub user : User,
     pub labels : HashMap<ID, Label>,
     pub projects : HashMap<ID, Project>,
+
+    /// archived projects, these are kept out of `projects`
+    pub archived_projects : HashMap<ID, Project>,
+
     pub items : HashMap<ID, Item>,
     pub collaborators : HashMap<ID, Collaborator>,
+    pub live_notifications : HashMap<ID, LiveNotification>,
+
+    /// notes attached to items
+    pub notes : HashMap<ID, Note>,
+
+    /// notes attached to projects
+    pub project_notes : HashMap<ID, ProjectNote>,
+
+    /// how the user is notified about each kind of event
+    pub notification_settings : HashMap<String, NotificationSetting>,
+
+    /// locations the user has set reminders at
+    pub locations : Vec<Location>,
 }
 
 impl Cache {
@@ -28,8 +46,14 @@ impl Cache {
             user: User::default(),
             labels: HashMap::new(),
             projects: HashMap::new(),
+            archived_projects: HashMap::new(),
             items: HashMap::new(),
             collaborators: HashMap::new(),
+            live_notifications: HashMap::new(),
+            notes: HashMap::new(),
+            project_notes: HashMap::new(),
+            notification_settings: HashMap::new(),
+            locations: Vec::new(),
         }
     }
 
@@ -47,14 +71,18 @@ impl Cache {
                                     ResourceType::Items,
                                     ResourceType::User,
                                     ResourceType::Collaborators,
-                                    ResourceType::Labels])?;
+                                    ResourceType::Labels,
+                                    ResourceType::Notes,
+                                    ResourceType::LiveNotifications,
+                                    ResourceType::NotificationSettings,
+                                    ResourceType::Locations])?;
 
         match resp.user {
             Some(v) => self.user = v,
             None => (),
         };
         for project in resp.projects.unwrap() {
-            self.projects.insert(project.id, project);
+            self.insert_project(project);
         }
 
         for item in resp.items.unwrap() {
@@ -68,10 +96,124 @@ impl Cache {
         for label in resp.labels.unwrap() {
             self.labels.insert(label.id, label);
         }
+
+        for notification in resp.live_notifications.unwrap() {
+            self.live_notifications.insert(notification.id, notification);
+        }
+
+        if let Some(notes) = resp.notes {
+            for note in notes {
+                self.notes.insert(note.id, note);
+            }
+        }
+
+        if let Some(notes) = resp.project_notes {
+            for note in notes {
+                self.project_notes.insert(note.id, note);
+            }
+        }
+
+        if let Some(settings) = resp.notification_settings {
+            self.notification_settings = settings;
+        }
+
+        if let Some(locations) = resp.locations {
+            self.locations = locations;
+        }
         self.sync_token = Some(resp.sync_token.clone());
         Ok(())
     }
 
+    /// Fetch the user's archived projects
+    pub fn sync_archived(&mut self, client : &Client) -> Result<(), Error> {
+        self.archived_projects.clear();
+        for project in client.archived_projects()? {
+            self.insert_project(project);
+        }
+        Ok(())
+    }
+
+    /// Add a project to `projects` or `archived_projects`, removing it from the other
+    fn insert_project(&mut self, project : Project) {
+        if project.is_archived {
+            self.projects.remove(&project.id);
+            self.archived_projects.insert(project.id, project);
+        } else {
+            self.archived_projects.remove(&project.id);
+            self.projects.insert(project.id, project);
+        }
+    }
+
+    /// Get an archived project by name
+    pub fn get_archived_project(&self, name : &str) -> Option<&Project> {
+        self.archived_projects.values()
+            .find(|p| p.name == name)
+    }
+
+    /// Update the cache with an item fetched by `Client::get_item`
+    ///
+    /// Returns false, and leaves the cache unchanged, if the data doesn't have a valid item.
+    pub fn merge_item(&mut self, data : ItemData) -> bool {
+        if data.item.id == 0 {
+            return false;
+        }
+        if let Some(project) = data.project {
+            if project.id != 0 {
+                self.insert_project(project);
+            }
+        }
+        for note in data.notes {
+            self.notes.insert(note.id, note);
+        }
+        self.items.insert(data.item.id, data.item);
+        true
+    }
+
+    /// Update the cache with a project fetched by `Client::get_project`
+    ///
+    /// Open items that are no longer in the project are removed, completed items are kept
+    /// since Todoist only sends the project's open items.
+    /// Returns false, and leaves the cache unchanged, if the data doesn't have a valid project.
+    pub fn merge_project(&mut self, data : ProjectData) -> bool {
+        let project_id = data.project.id;
+        if project_id == 0 {
+            return false;
+        }
+
+        let ids : HashSet<ID> = data.items.iter().map(|item| item.id).collect();
+        self.items.retain(|id, item| item.project_id != project_id || item.checked || ids.contains(id));
+        for item in data.items {
+            self.items.insert(item.id, item);
+        }
+        for note in data.notes {
+            self.project_notes.insert(note.id, note);
+        }
+        self.insert_project(data.project);
+        true
+    }
+
+    /// Update the cache with a label fetched by `Client::get_label`
+    ///
+    /// Returns false, and leaves the cache unchanged, if the label's ID is 0.
+    pub fn merge_label(&mut self, label : Label) -> bool {
+        if label.id == 0 {
+            return false;
+        }
+        self.labels.insert(label.id, label);
+        true
+    }
+
+    /// Update the cache with a note fetched by `Client::get_note`
+    ///
+    /// Returns false, and leaves the cache unchanged, if the note's ID is 0.
+    pub fn merge_note(&mut self, note : Note) -> bool {
+        if note.id == 0 {
+            return false;
+        }
+        self.notes.insert(note.id, note);
+        true
+    }
+
     /// Get a project from the cache using a file path, the path is made up of project names separated with a `/`
     /// 
     /// The path can have up to four elements (the maximum indent for a project is 4).
@@ -108,4 +250,37 @@ impl Cache {
         proj
     }
 
+    /// Evaluate a filter query against the cached items
+    ///
+    /// Returns one list of items for each comma separated view in the query.
+    pub fn query<'a>(&'a self, query : &Query) -> Vec<Vec<&'a Item>> {
+        query.eval(&Context::new(self))
+    }
+
+    /// Get the unread live notifications, oldest first
+    pub fn unread_notifications(&self) -> Vec<&LiveNotification> {
+        let mut unread : Vec<&LiveNotification> = self.live_notifications.values()
+            .filter(|n| n.is_unread)
+            .collect();
+        unread.sort_by_key(|n| n.seq_no);
+        unread
+    }
+
+    /// Get the notes attached to an item, oldest first
+    pub fn notes_for_item(&self, item_id : ID) -> Vec<&Note> {
+        let mut notes : Vec<&Note> = self.notes.values()
+            .filter(|n| n.item_id == item_id && !n.is_deleted)
+            .collect();
+        notes.sort_by_key(|n| (n.posting.as_ref().map(|d| d.timestamp), n.id));
+        notes
+    }
+
+    /// Get the notes attached to a project (not to its items), oldest first
+    pub fn notes_for_project(&self, project_id : ID) -> Vec<&ProjectNote> {
+        let mut notes : Vec<&ProjectNote> = self.project_notes.values()
+            .filter(|n| n.project_id == project_id && !n.is_deleted)
+            .collect();
+        notes.sort_by_key(|n| (n.posted.as_ref().map(|d| d.timestamp), n.id));
+        notes
+    }
 }
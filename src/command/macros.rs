@@ -8,6 +8,14 @@ macro_rules! command_arguments {
         command_arguments!($($typs $(<$($generics$(<$generics2>)* ),+>)*, $fields),*);
     };
 
+    (Option<IdRef>, $field:ident, $($typs:tt $(<$($generics:tt $(<$generics2:tt>)*),+>)*, $fields:ident),*) => {
+        pub fn $field<T : Into<IdRef>>(mut self, a : T) -> Self {
+            self.$field = Some(a.into());
+            self
+        }
+        command_arguments!($($typs $(<$($generics$(<$generics2>)* ),+>)*, $fields),*);
+    };
+
     (Option<$typ:tt $(<$generic:tt>)*>, $field:ident, $($typs:tt $(<$($generics:tt $(<$generics2:tt>)*),+>)*, $fields:ident),*) => {
         pub fn $field(mut self, a : $typ) -> Self {
             self.$field = Some(a);
@@ -16,6 +24,14 @@ macro_rules! command_arguments {
         command_arguments!($($typs $(<$($generics$(<$generics2>)* ),+>)*, $fields),*);
     };
 
+    (IdRef, $field:ident, $($typs:tt $(<$($generics:tt $(<$generics2:tt>)*),+>)*, $fields:ident),*) => {
+        pub fn $field<T : Into<IdRef>>(mut self, a : T) -> Self {
+            self.$field = a.into();
+            self
+        }
+        command_arguments!($($typs $(<$($generics$(<$generics2>)* ),+>)*, $fields),*);
+    };
+
     (ID, $field:ident, $($typs:tt $(<$($generics:tt $(<$generics2:tt>)*),+>)*, $fields:ident),*) => {
         pub fn $field<T : Into<ID>>(mut self, a : T) -> Self {
             self.$field = a.into();
@@ -57,11 +73,12 @@ macro_rules! command_arguments {
 }
 
 macro_rules! command {
-    (pub struct $name:ident { $($field:ident : $typ:tt $(<$($generic:tt $(<$generic2:tt>)*),+>)* ),+ }) => {
+    (pub struct $name:ident { $($(#[$attr:meta])* $field:ident : $typ:tt $(<$($generic:tt $(<$generic2:tt>)*),+>)* ),+ }) => {
         #[derive(Serialize, Deserialize, Default, Debug)]
         #[serde(default)]
         pub struct $name {
             $(
+                $(#[$attr])*
                 pub $field : $typ $(<$($generic$(<$generic2>)* ),+>)*,
             )*
         }
@@ -72,6 +89,47 @@ macro_rules! command {
     };
 }
 
+/// Like `command!`, but every field (other than the ID) is a `Patch`, and only fields that have been set are sent
+macro_rules! update_command {
+    (@build $name:ident { $($key:ident : $key_typ:ty),* } $($(#[$attr:meta])* $field:ident : $typ:ty),+) => {
+        #[derive(Serialize, Deserialize, Default, Debug)]
+        #[serde(default)]
+        pub struct $name {
+            $(
+                pub $key : $key_typ,
+            )*
+            $(
+                #[serde(skip_serializing_if = "Patch::is_unchanged")]
+                $(#[$attr])*
+                pub $field : Patch<$typ>,
+            )+
+        }
+
+        impl $name {
+            $(
+                pub fn $key<T : Into<$key_typ>>(mut self, a : T) -> Self {
+                    self.$key = a.into();
+                    self
+                }
+            )*
+            $(
+                pub fn $field<T : Into<Patch<$typ>>>(mut self, a : T) -> Self {
+                    self.$field = a.into();
+                    self
+                }
+            )+
+        }
+    };
+
+    (pub struct $name:ident { id : ID, $($(#[$attr:meta])* $field:ident : $typ:ty),+ }) => {
+        update_command!(@build $name { id : ID } $($(#[$attr])* $field : $typ),+);
+    };
+
+    (pub struct $name:ident { $($(#[$attr:meta])* $field:ident : $typ:ty),+ }) => {
+        update_command!(@build $name { } $($(#[$attr])* $field : $typ),+);
+    };
+}
+
 macro_rules! identity_list_command {
     ($name:ident) => (
         command! {
@@ -1,9 +1,13 @@
 use uuid::Uuid;
 use types::ID;
 #[macro_use] mod macros;
+mod validate;
+
+pub use self::validate::ValidationErrors;
 
 pub mod filter {
-    use types::{Color, ID};
+    use types::{Color, ID, Patch};
+    use types::serde_helpers;
     
     command! {
         pub struct Add {
@@ -11,18 +15,20 @@ pub mod filter {
             query : String,
             color : Color,
             item_order  : isize,
-            is_favorite : isize
+            #[serde(serialize_with = "serde_helpers::bool_as_int", deserialize_with = "serde_helpers::bool_or_int")]
+            is_favorite : bool
         }
     }
 
-    command! {
+    update_command! {
         pub struct Update {
             id          : ID,
             name        : String,
             query       : String,
             color       : Color,
             item_order  : isize,
-            is_favorite : isize
+            #[serde(serialize_with = "serde_helpers::patch_bool_as_int", deserialize_with = "serde_helpers::patch_bool_or_int")]
+            is_favorite : bool
         }
     }
 
@@ -31,20 +37,23 @@ pub mod filter {
 
 pub mod item {
     use types::*;
+    use types::serde_helpers;
     use std::collections::HashMap;
 
     command! {
         pub struct Add {
-             project_id : ID,
+             project_id : IdRef,
              content : Option<String>,
              date_string : Option<String>,
              date_lang : Option<Language>,
              due_date_utc : Option<Date>,
-             priority : Priority,
+             #[serde(skip_serializing_if = "Option::is_none")]
+             priority : Option<Priority>,
              indent : u8,
              item_order : isize,
              day_order : isize,
-             collapsed : isize,
+             #[serde(serialize_with = "serde_helpers::bool_as_int", deserialize_with = "serde_helpers::bool_or_int")]
+             collapsed : bool,
              labels : Vec<ID>,
              assigned_by_uid : Option<ID>,
              auto_reminders : Option<bool>,
@@ -52,21 +61,22 @@ pub mod item {
         }
     }
 
-    command! {
+    update_command! {
         pub struct Update {
              id : ID,
-             content : Option<String>,
-             date_string : Option<String>,
-             date_lang : Option<Language>,
-             due_date_utc : Option<Date>,
+             content : String,
+             date_string : String,
+             date_lang : Language,
+             due_date_utc : Date,
              priority : Priority,
              indent : u8,
              item_order : isize,
              day_order : isize,
-             collapsed : isize,
+             #[serde(serialize_with = "serde_helpers::patch_bool_as_int", deserialize_with = "serde_helpers::patch_bool_or_int")]
+             collapsed : bool,
              labels : Vec<ID>,
-             assigned_by_uid : Option<ID>,
-             responsible_uid : Option<ID>
+             assigned_by_uid : ID,
+             responsible_uid : ID
         }
     }
 
@@ -86,23 +96,26 @@ pub mod item {
 
 pub mod label {
     use types::*;
+    use types::serde_helpers;
 
     command! {
         pub struct Add {
             name : String,
             color : Color,
             item_order : isize,
-            is_favorite : isize
+            #[serde(serialize_with = "serde_helpers::bool_as_int", deserialize_with = "serde_helpers::bool_or_int")]
+            is_favorite : bool
         }
     }
 
-    command! {
+    update_command! {
         pub struct Update {
             id   : ID,
             name : String,
             color : Color,
             item_order : isize,
-            is_favorite : isize
+            #[serde(serialize_with = "serde_helpers::patch_bool_as_int", deserialize_with = "serde_helpers::patch_bool_or_int")]
+            is_favorite : bool
         }
     }
 
@@ -116,19 +129,19 @@ pub mod note {
 
     command! {
         pub struct Add {
-            item_id         : Option<ID>,
-            project_id      : Option<ID>,
+            item_id         : Option<IdRef>,
+            project_id      : Option<IdRef>,
             content         : String,
             file_attachment : Option<Attachment>,
             uids_to_notify  : Option<Vec<ID> >
         }
     }
 
-    command! {
+    update_command! {
         pub struct Update {
             id : ID,
             content         : String,
-            file_attachment : Option<Attachment>
+            file_attachment : Attachment
         }
     }
 
@@ -138,6 +151,7 @@ pub mod note {
 
 pub mod project {
     use types::*;
+    use types::serde_helpers;
 
     command! {
         pub struct Add {
@@ -145,23 +159,163 @@ pub mod project {
             color       : Color,
             indent      : u8,
             item_order  : isize,
-            is_favorite : isize
+            #[serde(serialize_with = "serde_helpers::bool_as_int", deserialize_with = "serde_helpers::bool_or_int")]
+            is_favorite : bool
         }
     }
 
-    command! {
+    update_command! {
         pub struct Update {
             id          : ID,
             name        : String,
             color       : Color,
             indent      : u8,
             item_order  : isize,
-            collapsed   : isize,
-            is_favorite : isize
+            #[serde(serialize_with = "serde_helpers::patch_bool_as_int", deserialize_with = "serde_helpers::patch_bool_or_int")]
+            collapsed   : bool,
+            #[serde(serialize_with = "serde_helpers::patch_bool_as_int", deserialize_with = "serde_helpers::patch_bool_or_int")]
+            is_favorite : bool
         }
     }
 
     identity_list_command!(Delete);
+    identity_list_command!(Archive);
+    identity_list_command!(Unarchive);
+}
+
+pub mod user {
+    use types::Patch;
+    use resource::NotificationService;
+
+    update_command! {
+        pub struct Update {
+            full_name        : String,
+            timezone         : String,
+            start_page       : String,
+            start_day        : isize,
+            next_week        : isize,
+            time_format      : isize,
+            date_format      : isize,
+            sort_order       : isize,
+            default_reminder : NotificationService,
+            auto_reminder    : isize,
+            theme            : isize
+        }
+    }
+
+    command! {
+        pub struct UpdateNotificationSetting {
+            notification_type : String,
+            service           : NotificationService,
+            dont_notify       : bool
+        }
+    }
+}
+
+pub mod live_notification {
+    use types::*;
+
+    command! {
+        pub struct MarkRead {
+            id : ID
+        }
+    }
+
+    command! {
+        pub struct MarkUnread {
+            id : ID
+        }
+    }
+
+    command! {
+        pub struct SetLastRead {
+            id : ID
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Default, Debug)]
+    pub struct MarkReadAll {}
+}
+
+pub mod invitation {
+    use types::*;
+
+    command! {
+        pub struct Accept {
+            invitation_id     : ID,
+            invitation_secret : String
+        }
+    }
+
+    command! {
+        pub struct Reject {
+            invitation_id     : ID,
+            invitation_secret : String
+        }
+    }
+
+    command! {
+        pub struct BusinessAccept {
+            invitation_id     : ID,
+            invitation_secret : String
+        }
+    }
+
+    command! {
+        pub struct BusinessReject {
+            invitation_id     : ID,
+            invitation_secret : String
+        }
+    }
+}
+
+pub mod reminder {
+    use types::*;
+    use resource::{NotificationService, NotificationTrigger, NotificationType};
+
+    command! {
+        pub struct Add {
+            item_id       : ID,
+            notify_uid    : Option<ID>,
+            service       : Option<NotificationService>,
+            #[serde(rename = "type")]
+            typ           : Option<NotificationType>,
+            date_string   : Option<String>,
+            date_lang     : Option<Language>,
+            due_date_utc  : Option<Date>,
+            minute_offset : Option<isize>,
+            name          : Option<String>,
+            loc_lat       : Option<f64>,
+            loc_long      : Option<f64>,
+            loc_trigger   : Option<NotificationTrigger>,
+            radius        : Option<isize>
+        }
+    }
+
+    update_command! {
+        pub struct Update {
+            id            : ID,
+            notify_uid    : ID,
+            service       : NotificationService,
+            #[serde(rename = "type")]
+            typ           : NotificationType,
+            date_string   : String,
+            date_lang     : Language,
+            due_date_utc  : Date,
+            minute_offset : isize,
+            name          : String,
+            loc_lat       : f64,
+            loc_long      : f64,
+            loc_trigger   : NotificationTrigger,
+            radius        : isize
+        }
+    }
+
+    command! {
+        pub struct Delete {
+            id : ID
+        }
+    }
 }
 
 make_argument_enum! {
@@ -187,7 +341,26 @@ make_argument_enum! {
 
         ProjectAdd(project::Add),
         ProjectUpdate(project::Update),
-        ProjectDelete(project::Delete)
+        ProjectDelete(project::Delete),
+        ProjectArchive(project::Archive),
+        ProjectUnarchive(project::Unarchive),
+
+        ReminderAdd(reminder::Add),
+        ReminderUpdate(reminder::Update),
+        ReminderDelete(reminder::Delete),
+
+        UserUpdate(user::Update),
+        UpdateNotificationSetting(user::UpdateNotificationSetting),
+
+        LiveNotificationsMarkRead(live_notification::MarkRead),
+        LiveNotificationsMarkUnread(live_notification::MarkUnread),
+        LiveNotificationsMarkReadAll(live_notification::MarkReadAll),
+        LiveNotificationsSetLastRead(live_notification::SetLastRead),
+
+        AcceptInvitation(invitation::Accept),
+        RejectInvitation(invitation::Reject),
+        BizAcceptInvitation(invitation::BusinessAccept),
+        BizRejectInvitation(invitation::BusinessReject)
     }
 }
 
@@ -0,0 +1,65 @@
+use super::CommandArgs;
+use resource::User;
+use types::Color;
+
+use std::error::Error;
+use std::fmt;
+
+/// Commands in a transaction that the user's account can't perform
+#[derive(Default, Debug)]
+pub struct ValidationErrors {
+    /// the index of each invalid command in the transaction, and why it's invalid
+    pub errors : Vec<(usize, String)>,
+}
+
+impl CommandArgs {
+    /// Check that a user's account can perform this command
+    ///
+    /// Free accounts can't use labels, filters, reminders or premium colors,
+    /// sending those commands anyway fails with an unhelpful error from the server.
+    pub fn validate(&self, user : &User) -> Result<(), String> {
+        if user.is_premium {
+            return Ok(());
+        }
+
+        match self {
+            &CommandArgs::ProjectAdd(ref c) => check_color("project", &c.color),
+            &CommandArgs::ProjectUpdate(ref c) => c.color.value().map(|color| check_color("project", color)).unwrap_or(Ok(())),
+            &CommandArgs::LabelAdd(_) | &CommandArgs::LabelUpdate(_) => premium("labels"),
+            &CommandArgs::FilterAdd(_) | &CommandArgs::FilterUpdate(_) => premium("filters"),
+            &CommandArgs::ReminderAdd(_) | &CommandArgs::ReminderUpdate(_) => premium("reminders"),
+            &CommandArgs::ItemAdd(ref c) if !c.labels.is_empty() => premium("labels"),
+            &CommandArgs::ItemAdd(ref c) if c.auto_reminders == Some(true) => premium("reminders"),
+            &CommandArgs::ItemUpdate(ref c) if c.labels.value().map(|l| !l.is_empty()).unwrap_or(false) => premium("labels"),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn check_color(what : &str, color : &Color) -> Result<(), String> {
+    if color.is_premium() {
+        Err(format!("the {} color \"{}\" is only available with Todoist Premium", what, color))
+    } else {
+        Ok(())
+    }
+}
+
+fn premium(what : &str) -> Result<(), String> {
+    Err(format!("{} are only available with Todoist Premium", what))
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} command(s) can't be sent: \n", self.errors.len())?;
+        for &(i, ref e) in self.errors.iter() {
+            write!(f, " - command {}: {}\n", i + 1, e)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ValidationErrors {
+    fn description(&self) -> &'static str {
+        "One or more commands are invalid"
+    }
+}
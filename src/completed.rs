@@ -0,0 +1,92 @@
+//! Completed items
+//!
+//! Once an item is completed and archived it's no longer returned by `/sync`,
+//! these types are used to request it from Todoist's completed items history instead.
+use types::*;
+use resource::{Item, Project};
+
+use serde::{Deserialize, Deserializer};
+
+use std::collections::HashMap;
+
+/// Filters for a page of completed items, Todoist allows up to 50 items in a page
+pub type CompletedFilter = Paged<CompletedItemsFilter>;
+
+/// The filters specific to completed items, see `CompletedFilter`
+#[derive(Debug, Clone, Default)]
+pub struct CompletedItemsFilter {
+    /// only get items in this project
+    pub project_id : Option<ID>,
+}
+
+/// An item that has been completed
+#[derive(Debug, Clone)]
+pub struct CompletedItem {
+    /// The completed item, only its ID, owner, project and content are known
+    pub item : Item,
+
+    /// when the item was completed
+    pub completed : Date,
+
+    /// the number of notes attached to the item
+    pub note_count : usize,
+}
+
+/// A page of completed items
+#[derive(Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct CompletedItems {
+    pub items : Vec<CompletedItem>,
+
+    /// the projects the items belong to, including archived projects
+    pub projects : HashMap<ID, Project>,
+}
+
+/// A completed item, as Todoist sends it
+#[derive(Deserialize)]
+struct CompletedRecord {
+    task_id : ID,
+    user_id : ID,
+    project_id : ID,
+    content : String,
+    completed_date : Date,
+
+    #[serde(default)]
+    note_count : usize,
+}
+
+impl Paged<CompletedItemsFilter> {
+    pub fn project_id(mut self, id : ID) -> Self {
+        self.filter.project_id = Some(id);
+        self
+    }
+}
+
+impl PageFilter for CompletedItemsFilter {
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(id) = self.project_id {
+            params.push(("project_id", id.to_string()));
+        }
+        params
+    }
+}
+
+impl<'de> Deserialize<'de> for CompletedItem {
+    fn deserialize<D : Deserializer<'de>>(deserializer: D) -> Result<CompletedItem, D::Error> {
+        let record = CompletedRecord::deserialize(deserializer)?;
+        Ok(CompletedItem {
+            item: Item {
+                id: record.task_id,
+                user_id: record.user_id,
+                project_id: record.project_id,
+                content: Some(record.content),
+                checked: true,
+                in_history: true,
+                ..Item::default()
+            },
+            completed: record.completed_date,
+            note_count: record.note_count,
+        })
+    }
+}
@@ -0,0 +1,304 @@
+//! Offline parsing of Todoist's free form date strings
+//!
+//! Todoist resolves strings like "next fri at 5pm" on the server, `DateParser` understands the
+//! common English forms so a date can be previewed (or used) without a round trip.
+mod recurrence;
+
+pub use self::recurrence::{Recurrence, Rule};
+
+use types::Date;
+use resource::User;
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveTime, TimeZone, Weekday};
+use std::error::Error;
+use std::fmt;
+
+/// Resolves date strings relative to the current time and a user's settings
+#[derive(Debug, Clone)]
+pub struct DateParser {
+    /// the time relative dates (e.g. "tomorrow") are resolved from
+    pub now : DateTime<FixedOffset>,
+
+    /// the first day of the week
+    pub start_day : Weekday,
+
+    /// the day of next week "next week" refers to
+    pub next_week : Weekday,
+
+    /// true if numeric dates are written day first (DD-MM-YYYY), false for MM-DD-YYYY
+    pub day_first : bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct DateStringError {
+    date_string : String,
+}
+
+impl DateParser {
+    /// Create a parser using a user's date settings, resolving dates relative to the current time in the user's time zone
+    pub fn new(user : &User) -> DateParser {
+        DateParser {
+            now: user.tz_info.now(),
+            start_day: weekday_setting(user.start_day),
+            next_week: weekday_setting(user.next_week),
+            day_first: user.date_format == 0,
+        }
+    }
+
+    /// Resolve dates relative to `now` instead of the current time
+    pub fn at(mut self, now : DateTime<FixedOffset>) -> DateParser {
+        self.now = now;
+        self
+    }
+
+    /// Parse a date string like "tomorrow", "in 3 days", "Oct 19" or "next fri at 5pm"
+    ///
+    /// Dates without a time are all-day dates.
+    pub fn parse(&self, s : &str) -> Result<Date, DateStringError> {
+        let err = || DateStringError { date_string: s.to_string() };
+        let lower = s.to_lowercase();
+        let mut words : Vec<&str> = lower.split(|c : char| c.is_whitespace() || c == ',')
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        let time = take_time(&mut words).map_err(|_| err())?;
+        words.retain(|w| !["at", "on", "of", "the"].contains(w));
+
+        let today = self.now.naive_local().date();
+        let date = match (words.is_empty(), time) {
+            (false, _) => self.parse_day(&words).ok_or_else(err)?,
+            (true, Some(t)) if t > self.now.naive_local().time() => today,
+            (true, Some(_)) => today + Duration::days(1),
+            (true, None) => return Err(err()),
+        };
+
+        match time {
+            Some(time) => {
+                let timestamp = self.now.offset().from_local_datetime(&date.and_time(time)).single().ok_or_else(err)?;
+                Ok(Date::from_timestamp(timestamp))
+            },
+            None => Ok(Date::from_date(date, self.now.offset())),
+        }
+    }
+
+    /// Parse the day portion of a date string (everything but the time)
+    fn parse_day(&self, words : &[&str]) -> Option<NaiveDate> {
+        let today = self.now.naive_local().date();
+
+        match words {
+            ["today"] | ["tod"]               => Some(today),
+            ["tomorrow"] | ["tom"] | ["tmr"]  => Some(today + Duration::days(1)),
+            ["yesterday"]                     => Some(today - Duration::days(1)),
+            ["next", "week"]                  => Some(self.in_next_week(self.next_week)),
+            ["weekend"] | ["this", "weekend"] => Some(next_weekday(today, Weekday::Sat)),
+            ["next", "weekend"]               => Some(self.in_next_week(Weekday::Sat)),
+            ["next", "month"]                 => Some(add_months(today, 1)),
+            ["next", "year"]                  => Some(add_months(today, 12)),
+            ["next", w]                       => weekday(w).map(|d| self.in_next_week(d)),
+            ["this", w]                       => weekday(w).map(|d| next_weekday(today, d)),
+            ["in", n, unit] | [n, unit] if count(n).and_then(|n| offset(today, n, unit)).is_some() => {
+                offset(today, count(n)?, unit)
+            },
+            [w] => weekday(w)
+                .map(|d| next_weekday(today, d))
+                .or_else(|| self.numeric_date(w)),
+            [a, b] => month_day(a, b).and_then(|(m, d)| upcoming(today, m, d)),
+            [a, b, y] => {
+                let (m, d) = month_day(a, b)?;
+                NaiveDate::from_ymd_opt(year(y)?, m, d)
+            },
+            _ => None,
+        }
+    }
+
+    /// the given weekday in the week after this one
+    fn in_next_week(&self, day : Weekday) -> NaiveDate {
+        let today = self.now.naive_local().date();
+        let since_start = days_between(self.start_day, today.weekday());
+        let next_start = today - Duration::days(since_start) + Duration::days(7);
+        next_start + Duration::days(days_between(self.start_day, day))
+    }
+
+    /// parse "2018-10-19", "19/10", "19/10/2018" or "10/19/18", depending on the user's date format
+    fn numeric_date(&self, s : &str) -> Option<NaiveDate> {
+        let parts : Vec<&str> = s.split(|c| c == '/' || c == '-' || c == '.').collect();
+        if parts.len() == 3 && parts[0].len() == 4 {
+            return NaiveDate::from_ymd_opt(parts[0].parse().ok()?, parts[1].parse().ok()?, parts[2].parse().ok()?);
+        }
+        if parts.len() != 2 && parts.len() != 3 {
+            return None;
+        }
+
+        let (a, b) : (u32, u32) = (parts[0].parse().ok()?, parts[1].parse().ok()?);
+        let (m, d) = if self.day_first { (b, a) } else { (a, b) };
+        match parts.get(2) {
+            Some(y) => NaiveDate::from_ymd_opt(year(y)?, m, d),
+            None => upcoming(self.now.naive_local().date(), m, d),
+        }
+    }
+}
+
+impl User {
+    /// Create a `DateParser` that follows this user's date settings
+    pub fn date_parser(&self) -> DateParser {
+        DateParser::new(self)
+    }
+}
+
+/// Remove the time from a list of words, e.g. "at 5pm", "17:30", "noon" or "9 am"
+fn take_time(words : &mut Vec<&str>) -> Result<Option<NaiveTime>, ()> {
+    for i in 0..words.len() {
+        let after_at = i > 0 && words[i - 1] == "at";
+        let suffix = words.get(i + 1).map(|w| *w == "am" || *w == "pm").unwrap_or(false);
+
+        let text = if suffix {
+            format!("{}{}", words[i], words[i + 1])
+        } else {
+            words[i].to_string()
+        };
+
+        let time = match text.as_str() {
+            "noon" => Some(NaiveTime::from_hms(12, 0, 0)),
+            "midnight" => Some(NaiveTime::from_hms(0, 0, 0)),
+            t if (t.ends_with("am") || t.ends_with("pm")) && t.starts_with(|c : char| c.is_digit(10)) => Some(clock(&t[..t.len() - 2], Some(t.ends_with("pm"))).ok_or(())?),
+            t if t.contains(':') || after_at => clock(t, None),
+            _ => None,
+        };
+
+        if let Some(time) = time {
+            let end = if suffix { i + 2 } else { i + 1 };
+            words.drain(i..end);
+            return Ok(Some(time));
+        }
+    }
+    Ok(None)
+}
+
+/// parse "5", "5:30" or "17:30", `pm` is `Some` for 12 hour times
+fn clock(s : &str, pm : Option<bool>) -> Option<NaiveTime> {
+    let mut parts = s.splitn(2, ':');
+    let hour : u32 = parts.next()?.parse().ok()?;
+    let minute : u32 = match parts.next() {
+        Some(m) => m.parse().ok()?,
+        None => 0,
+    };
+
+    let hour = match pm {
+        Some(_) if hour == 0 || hour > 12 => return None,
+        Some(true) => hour % 12 + 12,
+        Some(false) => hour % 12,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// parse a count like "3", "a" or "one"
+fn count(s : &str) -> Option<i64> {
+    match s {
+        "a" | "an" | "one" => Some(1),
+        "two" => Some(2),
+        "three" => Some(3),
+        _ => s.parse().ok(),
+    }
+}
+
+fn offset(date : NaiveDate, n : i64, unit : &str) -> Option<NaiveDate> {
+    match unit.trim_end_matches('s') {
+        "day"   => Some(date + Duration::days(n)),
+        "week"  => Some(date + Duration::weeks(n)),
+        "month" => Some(add_months(date, n)),
+        "year"  => Some(add_months(date, n * 12)),
+        _ => None,
+    }
+}
+
+/// Add a number of months to a date, clamping the day to the end of the month
+fn add_months(date : NaiveDate, n : i64) -> NaiveDate {
+    let months = date.year() as i64 * 12 + date.month0() as i64 + n;
+    let (y, m) = ((months / 12) as i32, (months % 12) as u32 + 1);
+    (1..date.day() + 1).rev()
+        .filter_map(|d| NaiveDate::from_ymd_opt(y, m, d))
+        .next()
+        .unwrap_or(date)
+}
+
+/// the next time a month and day occur, including today
+fn upcoming(today : NaiveDate, m : u32, d : u32) -> Option<NaiveDate> {
+    match NaiveDate::from_ymd_opt(today.year(), m, d) {
+        Some(date) if date >= today => Some(date),
+        _ => NaiveDate::from_ymd_opt(today.year() + 1, m, d),
+    }
+}
+
+/// the next time a weekday occurs, including today
+fn next_weekday(today : NaiveDate, day : Weekday) -> NaiveDate {
+    today + Duration::days(days_between(today.weekday(), day))
+}
+
+/// the number of days from one weekday to the next occurrence of another (0 - 6)
+fn days_between(from : Weekday, to : Weekday) -> i64 {
+    ((to.num_days_from_monday() + 7 - from.num_days_from_monday()) % 7) as i64
+}
+
+/// convert a weekday setting (1 is Monday, 7 is Sunday) to a `Weekday`
+fn weekday_setting(n : isize) -> Weekday {
+    (1..n).fold(Weekday::Mon, |day, _| day.succ())
+}
+
+fn year(s : &str) -> Option<i32> {
+    let y : i32 = s.parse().ok()?;
+    if s.len() == 2 { Some(2000 + y) } else { Some(y) }
+}
+
+/// parse "jan 3", "3 jan" or "3rd of january" into (month, day)
+pub(crate) fn month_day(a : &str, b : &str) -> Option<(u32, u32)> {
+    let (m, d) = match (month(a), month(b)) {
+        (Some(m), None) => (m, ordinal(b)?),
+        (None, Some(m)) => (m, ordinal(a)?),
+        _ => return None,
+    };
+    NaiveDate::from_ymd_opt(2000, m, d).map(|_| (m, d))
+}
+
+/// parse a day of the month, like "3" or "3rd"
+fn ordinal(s : &str) -> Option<u32> {
+    s.trim_end_matches(|c : char| c.is_alphabetic()).parse().ok()
+}
+
+/// parse a month name or abbreviation
+fn month(s : &str) -> Option<u32> {
+    const MONTHS : [&'static str; 12] = ["jan", "feb", "mar", "apr", "may", "jun",
+                                        "jul", "aug", "sep", "oct", "nov", "dec"];
+    if s.len() < 3 {
+        return None;
+    }
+    MONTHS.iter()
+        .position(|m| s.starts_with(m))
+        .map(|i| i as u32 + 1)
+}
+
+/// parse a weekday name or abbreviation
+pub(crate) fn weekday(s : &str) -> Option<Weekday> {
+    match s {
+        "mon" | "monday"    => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday"    => Some(Weekday::Fri),
+        "sat" | "saturday"  => Some(Weekday::Sat),
+        "sun" | "sunday"    => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+impl fmt::Display for DateStringError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown date \"{}\"", self.date_string)
+    }
+}
+
+impl Error for DateStringError {
+    fn description(&self) -> &'static str {
+        "unknown date"
+    }
+}
@@ -0,0 +1,212 @@
+use super::{DateStringError, take_time, ordinal, weekday, month_day, add_months, days_between};
+use types::Date;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Weekday};
+use std::str::FromStr;
+
+/// A recurring due date, e.g. "every other day" or "every 2nd monday"
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recurrence {
+    /// when the item recurs
+    pub rule : Rule,
+
+    /// how many days, weeks, months or years are between each occurrence (2 for "every other ...")
+    pub interval : u32,
+
+    /// the time of day the item is due, if the date string specifies one
+    pub time : Option<NaiveTime>,
+
+    /// true if the next occurrence is counted from when the item was completed ("every!")
+    pub from_completion : bool,
+}
+
+/// The pattern a recurring date follows
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    /// every day ("every day", "every 3 days")
+    Daily,
+
+    /// every day from Monday to Friday ("every workday", "every 2 workdays")
+    Workday,
+
+    /// on the given weekdays, or the starting weekday if empty ("every week", "every mon, fri")
+    Weekly(Vec<Weekday>),
+
+    /// on a day of the month, or the starting day if `None` ("every month", "every 15th")
+    Monthly(Option<u32>),
+
+    /// on the last day of the month ("every last day")
+    MonthlyLastDay,
+
+    /// on the nth weekday of the month, -1 for the last ("every 2nd monday", "every last fri")
+    MonthlyWeekday(i32, Weekday),
+
+    /// on a month and day, or the starting date if `None` ("every year", "every jan 3")
+    Yearly(Option<(u32, u32)>),
+}
+
+impl Recurrence {
+    /// Parse a recurring date string, the string must start with "every" (or "ev")
+    pub fn parse(s : &str) -> Result<Recurrence, DateStringError> {
+        let err = || DateStringError { date_string: s.to_string() };
+        let lower = s.to_lowercase();
+        let mut words : Vec<&str> = lower.split(|c : char| c.is_whitespace() || c == ',')
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        let from_completion = match words.first() {
+            Some(&"every") | Some(&"ev") => false,
+            Some(&"every!") | Some(&"ev!") => true,
+            _ => return Err(err()),
+        };
+        words.remove(0);
+
+        let time = take_time(&mut words).map_err(|_| err())?;
+        words.retain(|w| !["at", "on", "of", "the", "and"].contains(w));
+
+        // a leading number is the interval, unless it's the day in a date ("every 3 jan")
+        let is_date = words.len() == 2 && month_day(words[0], words[1]).is_some();
+        let (interval, words) = match words.split_first() {
+            Some((&"other", rest)) => (2, rest),
+            Some((n, rest)) if !is_date && !rest.is_empty() && n.parse::<u32>().is_ok() => (n.parse().unwrap(), rest),
+            _ => (1, words.as_slice()),
+        };
+
+        let rule = parse_rule(words).ok_or_else(err)?;
+        if interval == 0 {
+            return Err(err());
+        }
+
+        Ok(Recurrence {
+            rule: rule,
+            interval: interval,
+            time: time,
+            from_completion: from_completion,
+        })
+    }
+
+    /// Check if a date string describes a recurring date
+    pub fn is_recurring(s : &str) -> bool {
+        let lower = s.trim_start().to_lowercase();
+        ["every ", "every! ", "ev ", "ev! "].iter().any(|p| lower.starts_with(p))
+    }
+
+    /// Get the next `n` occurrences after `start`
+    ///
+    /// Intervals are counted from `start`, so for "every other day" starting on a Monday
+    /// the occurrences are Wednesday, Friday, Sunday...
+    pub fn occurrences(&self, start : &Date, n : usize) -> Vec<Date> {
+        let all_day = start.all_day && self.time.is_none();
+        let start = start.timestamp;
+        let time = self.time.unwrap_or(start.naive_local().time());
+        let first = start.naive_local().date();
+
+        self.dates(first)
+            .map(|d| if all_day {
+                Date::from_date(d, start.offset())
+            } else {
+                Date::from_timestamp(start.offset().from_local_datetime(&d.and_time(time)).unwrap())
+            })
+            .filter(|d| d.timestamp > start)
+            .take(n)
+            .collect()
+    }
+
+    /// Get the first occurrence after `start`
+    pub fn next(&self, start : &Date) -> Option<Date> {
+        self.occurrences(start, 1).pop()
+    }
+
+    /// every date matching this rule, starting with the period containing `first`
+    fn dates<'a>(&'a self, first : NaiveDate) -> Box<dyn Iterator<Item = NaiveDate> + 'a> {
+        let step = self.interval as i64;
+        // stop looking after a few hundred periods, so rules that never match (e.g. "every feb 30") end
+        let periods = 0..500i64;
+
+        match self.rule {
+            Rule::Daily => Box::new(periods.map(move |i| first + Duration::days(i * step))),
+            Rule::Workday => Box::new((0..500 * 7 * step).map(move |i| first + Duration::days(i))
+                .filter(|d| d.weekday() != Weekday::Sat && d.weekday() != Weekday::Sun)
+                .step_by(step as usize)),
+            Rule::Weekly(ref days) if days.is_empty() => Box::new(periods.map(move |i| first + Duration::weeks(i * step))),
+            Rule::Weekly(ref days) => {
+                let mut offsets : Vec<i64> = days.iter().map(|d| days_between(Weekday::Mon, *d)).collect();
+                offsets.sort();
+                offsets.dedup();
+                let monday = first - Duration::days(days_between(Weekday::Mon, first.weekday()));
+                Box::new(periods.flat_map(move |i| {
+                    let week = monday + Duration::weeks(i * step);
+                    offsets.clone().into_iter().map(move |o| week + Duration::days(o))
+                }))
+            },
+            Rule::Monthly(day) => {
+                let day = day.unwrap_or(first.day());
+                Box::new(periods.map(move |i| clamp_day(add_months(first, i * step), day)))
+            },
+            Rule::MonthlyLastDay => Box::new(periods.map(move |i| clamp_day(add_months(first, i * step), 31))),
+            Rule::MonthlyWeekday(nth, day) => Box::new(periods
+                .filter_map(move |i| nth_weekday(add_months(first, i * step), nth, day))),
+            Rule::Yearly(date) => {
+                let (m, d) = date.unwrap_or((first.month(), first.day()));
+                Box::new(periods.filter_map(move |i| NaiveDate::from_ymd_opt(first.year() + (i * step) as i32, m, d)))
+            },
+        }
+    }
+}
+
+impl FromStr for Recurrence {
+    type Err = DateStringError;
+
+    fn from_str(s : &str) -> Result<Self, Self::Err> {
+        Recurrence::parse(s)
+    }
+}
+
+fn parse_rule(words : &[&str]) -> Option<Rule> {
+    match words {
+        ["day"] | ["days"]                => Some(Rule::Daily),
+        ["weekday"] | ["weekdays"] |
+        ["workday"] | ["workdays"]        => Some(Rule::Workday),
+        ["week"] | ["weeks"]              => Some(Rule::Weekly(Vec::new())),
+        ["weekend"]                       => Some(Rule::Weekly(vec![Weekday::Sat, Weekday::Sun])),
+        ["month"] | ["months"]            => Some(Rule::Monthly(None)),
+        ["year"] | ["years"]              => Some(Rule::Yearly(None)),
+        ["last", "day"]                   => Some(Rule::MonthlyLastDay),
+        ["last", w]                       => weekday(w).map(|d| Rule::MonthlyWeekday(-1, d)),
+        [n, w] if weekday(w).is_some() && ordinal(n).is_some() => {
+            ordinal(n).filter(|n| *n >= 1 && *n <= 5).map(|n| Rule::MonthlyWeekday(n as i32, weekday(w).unwrap()))
+        },
+        [n] if ordinal(n).is_some() && weekday(n).is_none() => {
+            ordinal(n).filter(|n| *n >= 1 && *n <= 31).map(|n| Rule::Monthly(Some(n)))
+        },
+        [a, b] if month_day(a, b).is_some() => month_day(a, b).map(|md| Rule::Yearly(Some(md))),
+        _ => {
+            let days : Option<Vec<Weekday>> = words.iter().map(|w| weekday(w)).collect();
+            days.filter(|d| !d.is_empty()).map(Rule::Weekly)
+        },
+    }
+}
+
+/// a date in the same month, with the day clamped to the end of the month
+fn clamp_day(date : NaiveDate, day : u32) -> NaiveDate {
+    (1..day + 1).rev()
+        .filter_map(|d| NaiveDate::from_ymd_opt(date.year(), date.month(), d))
+        .next()
+        .unwrap_or(date)
+}
+
+/// the nth weekday in the same month as `date`, -1 is the last
+fn nth_weekday(date : NaiveDate, nth : i32, day : Weekday) -> Option<NaiveDate> {
+    if nth < 0 {
+        let last = clamp_day(date, 31);
+        return Some(last - Duration::days(days_between(day, last.weekday())));
+    }
+
+    let first = NaiveDate::from_ymd(date.year(), date.month(), 1);
+    let found = first + Duration::days(days_between(first.weekday(), day)) + Duration::weeks(nth as i64 - 1);
+    if found.month() == date.month() {
+        Some(found)
+    } else {
+        None
+    }
+}
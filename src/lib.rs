@@ -7,9 +7,18 @@ extern crate serde_json;
 
 pub mod command;
 pub mod cache;
+pub mod query;
+pub mod date_string;
+pub mod completed;
+pub mod activity;
+pub mod stats;
+pub mod quick_add;
+pub mod template;
+pub mod backup;
 
 mod resource;
 mod types;
+mod upload;
 
 pub use types::*;
 pub use resource::*;
@@ -56,8 +65,8 @@ pub enum ResourceType {
     #[serde(rename = "reminders")]
     Reminders,
 
-    #[serde(rename = "location")]
-    Locations, // TODO
+    #[serde(rename = "locations")]
+    Locations,
 
     #[serde(rename = "user")]
     User,
@@ -69,7 +78,7 @@ pub enum ResourceType {
     Collaborators,
 
     #[serde(rename = "notification_settings")]
-    NotificationSettings, // TODO
+    NotificationSettings,
 }
 
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
@@ -110,10 +119,35 @@ pub struct SyncResponse {
     pub projects : Option<Vec<resource::Project>>,
     pub collaborators : Option<Vec<resource::Collaborator>>,
     pub notes : Option<Vec<resource::Note>>,
+    pub project_notes : Option<Vec<resource::ProjectNote>>,
     pub filters : Option<Vec<resource::Filter>>,
     pub live_notifications : Option<Vec<resource::LiveNotification>>,
     pub reminders : Option<Vec<resource::Reminder>>,
+    pub locations : Option<Vec<resource::Location>>,
     pub user : Option<resource::User>,
+    pub notification_settings : Option<HashMap<String, resource::NotificationSetting>>,
+}
+
+/// An item, with its project and notes, returned by `Client::get_item`
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ItemData {
+    pub item : resource::Item,
+    pub project : Option<resource::Project>,
+
+    #[serde(default)]
+    pub notes : Vec<resource::Note>,
+}
+
+/// A project, with its items and notes, returned by `Client::get_project`
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProjectData {
+    pub project : resource::Project,
+
+    #[serde(default)]
+    pub items : Vec<resource::Item>,
+
+    #[serde(default)]
+    pub notes : Vec<resource::ProjectNote>,
 }
 
 /// Client to make request to the todoist API
@@ -122,6 +156,9 @@ pub struct Client {
     client: reqwest::Client,
 }
 
+/// The most commands Todoist accepts in a single request
+const MAX_COMMANDS : usize = 100;
+
 /// A transactions is a batch of commands that can be sent to Todoist in a single request
 /// 
 /// A transaction can be initiated with Client::begin(), to update the 
@@ -140,8 +177,80 @@ impl<'a> Transaction<'a> {
         self
     }
 
+    /// Add a command that creates an object, returning a reference to the new object
+    ///
+    /// The reference can be used by later commands in this transaction, e.g. to add items to a new project.
+    pub fn create<T : Into<command::CommandArgs>>(&mut self, args : T) -> IdRef {
+        let temp_id = uuid::Uuid::new_v4();
+        self.commands.push(command::Command {
+                args: args.into(),
+                temp_id: Some(temp_id),
+                uuid: uuid::Uuid::new_v4(),
+            });
+        IdRef::Temp(temp_id)
+    }
+
+    /// Check that the user's account can perform every command in this transaction
+    ///
+    /// This catches commands that need Todoist Premium, before they're sent.
+    pub fn validate(&self, user : &resource::User) -> Result<(), command::ValidationErrors> {
+        let errs = command::ValidationErrors {
+            errors: self.commands.iter()
+                .enumerate()
+                .filter_map(|(i, cmd)| cmd.args.validate(user).err().map(|e| (i, e)))
+                .collect(),
+        };
+        if errs.errors.len() > 0 {
+            Err(errs)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Send every command in this transaction
+    ///
+    /// Todoist accepts at most 100 commands per request, so larger transactions are sent in chunks.
+    /// References to objects created in an earlier chunk are replaced with their real IDs.
+    /// If a chunk fails, the chunks before it have already been applied.
     pub fn commit(self) -> Result<CommandResponse, types::Error> {
-        self.client.send(self.commands.as_slice())
+        let mut res = CommandResponse::default();
+        for chunk in self.commands.chunks(MAX_COMMANDS) {
+            let mut cmds = serde_json::to_value(chunk)?;
+            if let serde_json::Value::Array(ref mut cmds) = cmds {
+                for cmd in cmds.iter_mut() {
+                    if let Some(args) = cmd.get_mut("args") {
+                        resolve_temp_ids(args, &res.temp_id_mappings);
+                    }
+                }
+            }
+
+            let chunk_res = self.client.send_json(&cmds)?;
+            res.sync_status.extend(chunk_res.sync_status);
+            res.temp_id_mappings.extend(chunk_res.temp_id_mappings);
+        }
+        Ok(res)
+    }
+}
+
+/// Replace temporary IDs with the real IDs Todoist assigned them
+fn resolve_temp_ids(value : &mut serde_json::Value, mappings : &HashMap<uuid::Uuid, ID>) {
+    use serde_json::Value;
+
+    let id = match value {
+        Value::String(s) => s.parse().ok().and_then(|temp_id : uuid::Uuid| mappings.get(&temp_id)).cloned(),
+        Value::Array(values) => {
+            values.iter_mut().for_each(|v| resolve_temp_ids(v, mappings));
+            None
+        },
+        Value::Object(values) => {
+            values.values_mut().for_each(|v| resolve_temp_ids(v, mappings));
+            None
+        },
+        _ => None,
+    };
+
+    if let Some(id) = id {
+        *value = Value::from(id as u64);
     }
 }
 
@@ -166,12 +275,159 @@ impl<'a> Client {
         Ok(res)
     }
 
+    /// Add an item using quick add syntax, e.g. `Buy milk tomorrow #Errands @store p2`
+    ///
+    /// The text is parsed by Todoist, see `quick_add::QuickAdd` to parse it locally instead.
+    pub fn quick_add(&self, text : &str) -> Result<resource::Item, types::Error> {
+        let res : resource::Item = self.client.post("http://todoist.com/api/v7/quick/add")
+            .form(&[("token", self.token.as_str()),
+                    ("text", text)])
+            .send()?
+            .error_for_status()?
+            .json()?;
+        Ok(res)
+    }
+
+    /// Request a single item, along with its project and notes
+    pub fn get_item(&self, id : ID) -> Result<ItemData, types::Error> {
+        let res : ItemData = self.client.post("http://todoist.com/api/v7/items/get")
+            .form(&[("token", self.token.clone()),
+                    ("item_id", id.to_string())])
+            .send()?
+            .error_for_status()?
+            .json()?;
+        Ok(res)
+    }
+
+    /// Request a single project, along with its items and notes
+    pub fn get_project(&self, id : ID) -> Result<ProjectData, types::Error> {
+        let params = [("token", self.token.clone()),
+                      ("project_id", id.to_string())];
+
+        // the project and its notes, then the project's items
+        let mut res : ProjectData = self.client.post("http://todoist.com/api/v7/projects/get")
+            .form(&params)
+            .send()?
+            .error_for_status()?
+            .json()?;
+        let data : ProjectData = self.client.post("http://todoist.com/api/v7/projects/get_data")
+            .form(&params)
+            .send()?
+            .error_for_status()?
+            .json()?;
+        res.items = data.items;
+        Ok(res)
+    }
+
+    /// Request a single label
+    pub fn get_label(&self, id : ID) -> Result<resource::Label, types::Error> {
+        #[derive(Deserialize)]
+        struct LabelData {
+            label : resource::Label,
+        }
+
+        let res : LabelData = self.client.post("http://todoist.com/api/v7/labels/get")
+            .form(&[("token", self.token.clone()),
+                    ("label_id", id.to_string())])
+            .send()?
+            .error_for_status()?
+            .json()?;
+        Ok(res.label)
+    }
+
+    /// Request a single note attached to an item
+    pub fn get_note(&self, id : ID) -> Result<resource::Note, types::Error> {
+        #[derive(Deserialize)]
+        struct NoteData {
+            note : resource::Note,
+        }
+
+        let res : NoteData = self.client.post("http://todoist.com/api/v7/notes/get")
+            .form(&[("token", self.token.clone()),
+                    ("note_id", id.to_string())])
+            .send()?
+            .error_for_status()?
+            .json()?;
+        Ok(res.note)
+    }
+
+    /// Request the user's archived projects, which aren't included in a sync
+    pub fn archived_projects(&self) -> Result<Vec<resource::Project>, types::Error> {
+        let res : Vec<resource::Project> = self.client.post("http://todoist.com/api/v7/projects/get_archived")
+            .form(&[("token", self.token.as_str())])
+            .send()?
+            .error_for_status()?
+            .json()?;
+        Ok(res)
+    }
+
+    /// List the backups Todoist has made of the user's projects
+    pub fn backups(&self) -> Result<Vec<backup::Backup>, types::Error> {
+        let res : Vec<backup::Backup> = self.client.post("http://todoist.com/api/v7/backups/get")
+            .form(&[("token", self.token.as_str())])
+            .send()?
+            .error_for_status()?
+            .json()?;
+        Ok(res)
+    }
+
+    /// Download a backup's zip archive, returning the number of bytes written
+    pub fn download_backup<P : AsRef<std::path::Path>>(&self, backup : &backup::Backup, path : P) -> Result<u64, types::Error> {
+        let mut file = std::fs::File::create(path)?;
+        let mut res = self.client.get(&backup.url)
+            .query(&[("token", self.token.as_str())])
+            .send()?
+            .error_for_status()?;
+        Ok(res.copy_to(&mut file)?)
+    }
+
+    /// Request a page of completed items
+    ///
+    /// This requires Todoist Premium.
+    pub fn completed_items(&self, filter : &completed::CompletedFilter) -> Result<completed::CompletedItems, types::Error> {
+        let mut params = filter.params();
+        params.push(("token", self.token.clone()));
+        let res : completed::CompletedItems = self.client.post("http://todoist.com/api/v7/completed/get_all")
+            .form(&params)
+            .send()?
+            .error_for_status()?
+            .json()?;
+        Ok(res)
+    }
+
+    /// Request a page of events from the activity log, most recent first
+    pub fn activity(&self, filter : &activity::ActivityFilter) -> Result<Vec<activity::ActivityEvent>, types::Error> {
+        let mut params = filter.params();
+        params.push(("token", self.token.clone()));
+        let res : Vec<activity::ActivityEvent> = self.client.post("http://todoist.com/api/v7/activity/get")
+            .form(&params)
+            .send()?
+            .error_for_status()?
+            .json()?;
+        Ok(res)
+    }
+
+    /// Request the user's productivity stats
+    pub fn productivity_stats(&self) -> Result<stats::ProductivityStats, types::Error> {
+        let res : stats::ProductivityStats = self.client.post("http://todoist.com/api/v7/completed/get_stats")
+            .form(&[("token", self.token.as_str())])
+            .send()?
+            .error_for_status()?
+            .json()?;
+        Ok(res)
+    }
+
     /// Send a series of commands to todoist
     /// 
     /// It is generally prettier and safer to use a transaction, instead of this command.
     /// See Client::begin()
     pub fn send(&mut self, cmd: &[command::Command]) -> Result<CommandResponse, types::Error> {
-        println!("{}", serde_json::to_string(cmd)?);
+        let cmd = serde_json::to_value(cmd)?;
+        self.send_json(&cmd)
+    }
+
+    /// Send a list of commands that have already been serialized
+    fn send_json(&mut self, cmd: &serde_json::Value) -> Result<CommandResponse, types::Error> {
         let res : CommandResponse = self.client.post("http://todoist.com/api/v7/sync")
             .form(&[("token", self.token.clone()), 
                     ("commands", serde_json::to_string(cmd)?)])
@@ -0,0 +1,190 @@
+use super::{Query, Expr, Term, DateSpec, Assignee};
+use cache::Cache;
+use resource::{Item, Project};
+use date_string::Recurrence;
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate};
+
+/// The state a query is evaluated against
+pub struct Context<'a> {
+    /// the cache used to look up projects, labels and collaborators
+    pub cache : &'a Cache,
+
+    /// the current time, used to resolve relative dates like "today"
+    pub now : DateTime<FixedOffset>,
+}
+
+impl<'a> Context<'a> {
+    /// Create a context for evaluating queries at the current time, in the user's time zone
+    pub fn new(cache : &'a Cache) -> Context<'a> {
+        Context {
+            cache: cache,
+            now: cache.user.tz_info.now(),
+        }
+    }
+
+    fn today(&self) -> NaiveDate {
+        self.now.naive_local().date()
+    }
+
+    fn resolve(&self, spec : &DateSpec) -> NaiveDate {
+        let today = self.today();
+        match spec {
+            &DateSpec::Today => today,
+            &DateSpec::Tomorrow => today + Duration::days(1),
+            &DateSpec::Yesterday => today - Duration::days(1),
+            &DateSpec::Weekday(day) => {
+                let diff = (day.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+                today + Duration::days(diff as i64)
+            },
+            &DateSpec::MonthDay(m, d) => NaiveDate::from_ymd_opt(today.year(), m, d).unwrap_or(today),
+            &DateSpec::Date(date) => date,
+        }
+    }
+
+    /// the day an item is due, in the context's time zone
+    fn due_date(&self, item : &Item) -> Option<NaiveDate> {
+        item.due(&self.cache.user.tz_info).map(|d| d.date())
+    }
+
+    fn project_tree(&self, root : &'a Project) -> Vec<&'a Project> {
+        let mut sorted : Vec<&Project> = self.cache.projects.values().collect();
+        sorted.sort_by_key(|p| p.item_order);
+
+        sorted.into_iter()
+            .skip_while(|p| p.id != root.id)
+            .enumerate()
+            .take_while(|&(i, p)| i == 0 || p.indent > root.indent)
+            .map(|(_, p)| p)
+            .collect()
+    }
+
+    fn assignee_matches(&self, uid : Option<usize>, who : &Assignee) -> bool {
+        let uid = match uid {
+            Some(v) => v,
+            None => return false,
+        };
+
+        match who {
+            &Assignee::Me => uid == self.cache.user.id,
+            &Assignee::Others => uid != self.cache.user.id,
+            &Assignee::Named(ref name) => {
+                if uid == self.cache.user.id {
+                    return glob_match(name, &self.cache.user.full_name) || glob_match(name, &self.cache.user.email);
+                }
+                match self.cache.collaborators.get(&uid) {
+                    Some(c) => glob_match(name, &c.full_name)
+                        || glob_match(name, c.full_name.split_whitespace().next().unwrap_or(""))
+                        || glob_match(name, &c.email),
+                    None => false,
+                }
+            },
+        }
+    }
+}
+
+impl Query {
+    /// Find the items in the cache that match each view of this query
+    ///
+    /// Completed and deleted items are never included. Items in each view are sorted by due date, then by their order.
+    pub fn eval<'a>(&self, ctx : &Context<'a>) -> Vec<Vec<&'a Item>> {
+        self.views.iter()
+            .map(|view| {
+                let mut items : Vec<&Item> = ctx.cache.items.values()
+                    .filter(|item| !item.checked && !item.is_deleted)
+                    .filter(|item| view.matches(item, ctx))
+                    .collect();
+                items.sort_by_key(|item| (item.due_date_utc.is_none(),
+                                         item.due_date_utc.as_ref().map(|d| d.timestamp),
+                                         item.item_order));
+                items
+            })
+            .collect()
+    }
+}
+
+impl Expr {
+    /// Check if an item matches this expression
+    pub fn matches(&self, item : &Item, ctx : &Context) -> bool {
+        match self {
+            &Expr::And(ref a, ref b) => a.matches(item, ctx) && b.matches(item, ctx),
+            &Expr::Or(ref a, ref b) => a.matches(item, ctx) || b.matches(item, ctx),
+            &Expr::Not(ref a) => !a.matches(item, ctx),
+            &Expr::Term(ref t) => t.matches(item, ctx),
+        }
+    }
+}
+
+impl Term {
+    /// Check if an item matches this term
+    pub fn matches(&self, item : &Item, ctx : &Context) -> bool {
+        match self {
+            &Term::Due(ref spec) => ctx.due_date(item) == Some(ctx.resolve(spec)),
+            &Term::DueBefore(ref spec) => ctx.due_date(item).map(|d| d < ctx.resolve(spec)).unwrap_or(false),
+            &Term::DueAfter(ref spec) => ctx.due_date(item).map(|d| d > ctx.resolve(spec)).unwrap_or(false),
+            &Term::Overdue => match item.due(&ctx.cache.user.tz_info) {
+                Some(ref d) if d.all_day => ctx.due_date(item).map(|d| d < ctx.today()).unwrap_or(false),
+                Some(ref d) => d.timestamp < ctx.now,
+                None => false,
+            },
+            &Term::NoDate => item.due_date_utc.is_none(),
+            &Term::Recurring => item.date_string.as_ref()
+                .map(|s| Recurrence::is_recurring(s))
+                .unwrap_or(false),
+            &Term::Days(n) => {
+                let today = ctx.today();
+                let (start, end) = if n >= 0 {
+                    (today, today + Duration::days(n))
+                } else {
+                    (today + Duration::days(n), today)
+                };
+                ctx.due_date(item).map(|d| d >= start && d < end).unwrap_or(false)
+            },
+            &Term::Priority(p) => item.priority == p,
+            &Term::Project(ref name) => ctx.cache.projects.get(&item.project_id)
+                .map(|p| glob_match(name, &p.name))
+                .unwrap_or(false),
+            &Term::ProjectTree(ref name) => ctx.cache.projects.values()
+                .filter(|p| glob_match(name, &p.name))
+                .any(|p| ctx.project_tree(p).iter().any(|child| child.id == item.project_id)),
+            &Term::Label(ref name) => item.labels.iter()
+                .filter_map(|id| ctx.cache.labels.get(id))
+                .any(|l| glob_match(name, &l.name)),
+            &Term::NoLabels => item.labels.is_empty(),
+            &Term::AssignedTo(ref who) => ctx.assignee_matches(item.responsible_uid, who),
+            &Term::AssignedBy(ref who) => ctx.assignee_matches(item.assigned_by_uid, who),
+            &Term::Assigned => item.responsible_uid.is_some(),
+            &Term::Shared => ctx.cache.projects.get(&item.project_id)
+                .map(|p| p.shared)
+                .unwrap_or(false),
+            &Term::Search(ref text) => item.content.as_ref()
+                .map(|c| c.to_lowercase().contains(&text.to_lowercase()))
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// Case insensitive comparison, where `*` in the pattern matches any number of characters
+fn glob_match(pattern : &str, text : &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let text = text.to_lowercase();
+    let parts : Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+
+    let first = parts[0];
+    let last = parts[parts.len() - 1];
+    if !text.starts_with(first) || !text[first.len()..].ends_with(last) {
+        return false;
+    }
+
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
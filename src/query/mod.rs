@@ -0,0 +1,133 @@
+//! Todoist filter queries
+//!
+//! Filters are written in Todoist's query language, e.g. `(today | overdue) & #Work & p1`.
+//! `Query::parse` turns that text into an expression tree which can be evaluated against
+//! the items in a `Cache` without talking to the server.
+mod parser;
+mod eval;
+
+pub use self::parser::ParseError;
+pub use self::eval::Context;
+
+use types::Priority;
+
+use chrono::{NaiveDate, Weekday};
+use std::str::FromStr;
+
+/// A parsed filter query
+///
+/// Queries separated by a comma are treated as separate views,
+/// e.g. `today, overdue` shows two lists: one for today and one for overdue items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub views : Vec<Expr>,
+}
+
+/// A boolean expression of filter terms
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Term(Term),
+}
+
+/// A single condition in a filter query
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    /// Items due on a given day, e.g. `today` or `Jan 3`
+    Due(DateSpec),
+
+    /// Items due before a given day, e.g. `due before: tomorrow`
+    DueBefore(DateSpec),
+
+    /// Items due after a given day, e.g. `due after: May 5`
+    DueAfter(DateSpec),
+
+    /// Items past their due date (`overdue` or `od`)
+    Overdue,
+
+    /// Items without a due date (`no date`)
+    NoDate,
+
+    /// Items with a recurring due date (`recurring`)
+    Recurring,
+
+    /// Items due within the next N days (`7 days`), or the last N days if negative (`-3 days`)
+    Days(i64),
+
+    /// Items with a given priority (`p1`)
+    Priority(Priority),
+
+    /// Items in a project, without its sub-projects (`#Work`)
+    Project(String),
+
+    /// Items in a project or any of its sub-projects (`##Work`)
+    ProjectTree(String),
+
+    /// Items with a label (`@waiting`)
+    Label(String),
+
+    /// Items without any labels (`no labels`)
+    NoLabels,
+
+    /// Items assigned to someone (`assigned to: me`)
+    AssignedTo(Assignee),
+
+    /// Items assigned by someone (`assigned by: me`)
+    AssignedBy(Assignee),
+
+    /// Items assigned to anyone (`assigned`)
+    Assigned,
+
+    /// Items in a shared project (`shared`)
+    Shared,
+
+    /// Items whose content contains some text (`search: milk`)
+    Search(String),
+}
+
+/// A day referenced in a filter query
+#[derive(Debug, Clone, PartialEq)]
+pub enum DateSpec {
+    Today,
+    Tomorrow,
+    Yesterday,
+
+    /// The next occurrence of a weekday (including today)
+    Weekday(Weekday),
+
+    /// A month and day in the current year
+    MonthDay(u32, u32),
+
+    /// A fully specified date
+    Date(NaiveDate),
+}
+
+/// A user referenced by an `assigned to:` or `assigned by:` term
+#[derive(Debug, Clone, PartialEq)]
+pub enum Assignee {
+    /// The current user
+    Me,
+
+    /// Anyone but the current user
+    Others,
+
+    /// A collaborator, matched by name or email
+    Named(String),
+}
+
+impl Query {
+    /// Parse a filter query
+    pub fn parse(s : &str) -> Result<Query, ParseError> {
+        parser::parse(s)
+    }
+}
+
+impl FromStr for Query {
+    type Err = ParseError;
+
+    fn from_str(s : &str) -> Result<Self, Self::Err> {
+        Query::parse(s)
+    }
+}
@@ -0,0 +1,300 @@
+use super::{Query, Expr, Term, DateSpec, Assignee};
+use date_string::{month_day, weekday};
+use types::Priority;
+
+use chrono::NaiveDate;
+use std::error::Error;
+use std::fmt;
+
+/// An error encountered while parsing a filter query
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    /// the character offset the error occurred at
+    pub position : usize,
+
+    /// a description of the problem
+    pub message : String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Text(String),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+    Comma,
+}
+
+struct Parser {
+    tokens : Vec<(usize, Token)>,
+    pos : usize,
+    end : usize,
+}
+
+pub fn parse(s : &str) -> Result<Query, ParseError> {
+    let mut parser = Parser {
+        tokens: tokenize(s)?,
+        pos: 0,
+        end: s.chars().count(),
+    };
+
+    let mut views = vec![parser.expr()?];
+    while parser.peek() == Some(&Token::Comma) {
+        parser.pos += 1;
+        views.push(parser.expr()?);
+    }
+
+    match parser.tokens.get(parser.pos) {
+        None => Ok(Query { views: views }),
+        Some(&(i, ref tok)) => Err(ParseError::new(i, format!("unexpected {:?}", tok))),
+    }
+}
+
+/// Split a query into operators and terms.
+///
+/// Anything that isn't an operator is part of a term, so names like `#Work Stuff` stay together.
+/// `!` and `(` are only treated as operators at the start of a term, and a `\` escapes the next character.
+fn tokenize(s : &str) -> Result<Vec<(usize, Token)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut start = 0;
+    let mut chars = s.chars().enumerate();
+    // open groups, and parentheses that are part of the current text (e.g. "#Work (old)")
+    let mut depth = 0;
+    let mut nested = 0;
+
+    while let Some((i, c)) = chars.next() {
+        let op = match c {
+            '&' => Some(Token::And),
+            '|' => Some(Token::Or),
+            ')' if nested == 0 && depth > 0 => Some(Token::Close),
+            ',' => Some(Token::Comma),
+            '!' if text.trim().is_empty() => Some(Token::Not),
+            '(' if text.trim().is_empty() => Some(Token::Open),
+            _   => None,
+        };
+
+        match op {
+            Some(tok) => {
+                match tok {
+                    Token::Open => depth += 1,
+                    Token::Close => depth -= 1,
+                    _ => (),
+                }
+                push_text(&mut tokens, &mut text, start);
+                nested = 0;
+                tokens.push((i, tok));
+            },
+            None if c == '\\' => match chars.next() {
+                Some((_, escaped)) => text.push(escaped),
+                None => return Err(ParseError::new(i, "dangling escape character")),
+            },
+            None => {
+                if text.trim().is_empty() {
+                    start = i;
+                }
+                match c {
+                    '(' => nested += 1,
+                    ')' if nested > 0 => nested -= 1,
+                    _ => (),
+                }
+                text.push(c);
+            },
+        }
+    }
+    push_text(&mut tokens, &mut text, start);
+    Ok(tokens)
+}
+
+fn push_text(tokens : &mut Vec<(usize, Token)>, text : &mut String, start : usize) {
+    let trimmed = text.trim().to_string();
+    if !trimmed.is_empty() {
+        tokens.push((start, Token::Text(trimmed)));
+    }
+    text.clear();
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|&(_, ref t)| t)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens.get(self.pos).map(|&(i, _)| i).unwrap_or(self.end)
+    }
+
+    // expr := and ('|' and)*
+    fn expr(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            let rhs = self.and()?;
+            lhs = Expr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    // and := unary ('&' unary)*
+    fn and(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            let rhs = self.unary()?;
+            lhs = Expr::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    // unary := '!' unary | '(' expr ')' | term
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        let position = self.position();
+        let tok = self.tokens.get(self.pos).map(|&(_, ref t)| t.clone());
+        self.pos += 1;
+
+        match tok {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some(Token::Open) => {
+                let inner = self.expr()?;
+                if self.peek() != Some(&Token::Close) {
+                    return Err(ParseError::new(self.position(), "expected \")\""));
+                }
+                self.pos += 1;
+                Ok(inner)
+            },
+            Some(Token::Text(s)) => parse_term(&s).map(Expr::Term).map_err(|e| ParseError::new(position, e)),
+            Some(tok) => Err(ParseError::new(position, format!("unexpected {:?}", tok))),
+            None => Err(ParseError::new(position, "unexpected end of query")),
+        }
+    }
+}
+
+fn parse_term(s : &str) -> Result<Term, String> {
+    let lower = s.to_lowercase().split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if s.starts_with("##") {
+        return name(&s[2..]).map(Term::ProjectTree);
+    } else if s.starts_with('#') {
+        return name(&s[1..]).map(Term::Project);
+    } else if s.starts_with('@') {
+        return name(&s[1..]).map(Term::Label);
+    }
+
+    if let Some(i) = s.find(':') {
+        let value = s[i + 1..].trim();
+        return match lower[..lower.find(':').unwrap()].trim() {
+            "due" | "date"                => parse_date(value).map(Term::Due),
+            "due before" | "date before"  => parse_date(value).map(Term::DueBefore),
+            "due after"  | "date after"   => parse_date(value).map(Term::DueAfter),
+            "assigned to"                 => parse_assignee(value).map(Term::AssignedTo),
+            "assigned by"                 => parse_assignee(value).map(Term::AssignedBy),
+            "search"                      => name(value).map(Term::Search),
+            other => Err(format!("unknown filter \"{}:\"", other)),
+        };
+    }
+
+    match lower.as_str() {
+        "p1" => return Ok(Term::Priority(Priority::P1)),
+        "p2" => return Ok(Term::Priority(Priority::P2)),
+        "p3" => return Ok(Term::Priority(Priority::P3)),
+        "p4" => return Ok(Term::Priority(Priority::P4)),
+        "overdue" | "od"          => return Ok(Term::Overdue),
+        "no date" | "no due date" => return Ok(Term::NoDate),
+        "recurring"               => return Ok(Term::Recurring),
+        "no labels"               => return Ok(Term::NoLabels),
+        "assigned"                => return Ok(Term::Assigned),
+        "shared"                  => return Ok(Term::Shared),
+        _ => (),
+    }
+
+    if let Some(n) = parse_days(&lower) {
+        return Ok(Term::Days(n));
+    }
+
+    parse_date(s).map(Term::Due).map_err(|_| format!("unknown filter term \"{}\"", s))
+}
+
+fn name(s : &str) -> Result<String, String> {
+    let s = s.trim();
+    if s.is_empty() {
+        Err("expected a name".to_string())
+    } else {
+        Ok(s.to_string())
+    }
+}
+
+/// parse `N days`, `next N days` or `-N days`
+fn parse_days(s : &str) -> Option<i64> {
+    let words : Vec<&str> = s.split(' ').collect();
+    let n = match words.as_slice() {
+        [n, "days"] | [n, "day"] => n,
+        ["next", n, "days"] | ["next", n, "day"] => n,
+        _ => return None,
+    };
+    n.parse().ok()
+}
+
+fn parse_assignee(s : &str) -> Result<Assignee, String> {
+    match s.to_lowercase().as_str() {
+        "me"     => Ok(Assignee::Me),
+        "others" => Ok(Assignee::Others),
+        _        => name(s).map(Assignee::Named),
+    }
+}
+
+fn parse_date(s : &str) -> Result<DateSpec, String> {
+    let lower = s.to_lowercase();
+    let words : Vec<&str> = lower.split(|c : char| c.is_whitespace() || c == ',')
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    match words.as_slice() {
+        ["today"] | ["tod"]     => return Ok(DateSpec::Today),
+        ["tomorrow"] | ["tom"]  => return Ok(DateSpec::Tomorrow),
+        ["yesterday"]           => return Ok(DateSpec::Yesterday),
+        [w] => {
+            if let Some(day) = weekday(w) {
+                return Ok(DateSpec::Weekday(day));
+            }
+            if let Ok(date) = NaiveDate::parse_from_str(w, "%Y-%m-%d") {
+                return Ok(DateSpec::Date(date));
+            }
+        },
+        [a, b] => {
+            if let Some((m, d)) = month_day(a, b) {
+                return Ok(DateSpec::MonthDay(m, d));
+            }
+        },
+        [a, b, y] => {
+            if let (Some((m, d)), Ok(y)) = (month_day(a, b), y.parse()) {
+                if let Some(date) = NaiveDate::from_ymd_opt(y, m, d) {
+                    return Ok(DateSpec::Date(date));
+                }
+            }
+        },
+        _ => (),
+    }
+    Err(format!("unknown date \"{}\"", s))
+}
+
+impl ParseError {
+    fn new<S : Into<String>>(position : usize, message : S) -> ParseError {
+        ParseError {
+            position: position,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid filter query (at {}): {}", self.position, self.message)
+    }
+}
+
+impl Error for ParseError {
+    fn description(&self) -> &'static str {
+        "invalid filter query"
+    }
+}
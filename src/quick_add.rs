@@ -0,0 +1,194 @@
+//! Todoist's quick add syntax
+//!
+//! Quick add lets a task's details be written inline, e.g. `Buy milk tomorrow #Errands @store p2`:
+//! `#` sets the project, `@` adds labels, `p1` to `p4` set the priority, and a date at the start or end sets the due date.
+//! Unlike `Client::quick_add`, these are parsed locally, so they work without a connection.
+use types::*;
+use cache::Cache;
+use command;
+use date_string::{DateParser, Recurrence};
+
+use std::error::Error;
+use std::fmt;
+
+/// A task written in quick add syntax, split into its parts
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuickAdd {
+    /// The task's text, without any of the other parts
+    pub content : String,
+
+    /// The project's name or path, e.g. "Work/Meetings"
+    pub project : Option<String>,
+
+    /// The labels' names
+    pub labels : Vec<String>,
+
+    pub priority : Option<Priority>,
+
+    /// The due date, exactly as it was written
+    pub date_string : Option<String>,
+}
+
+/// A project or label in a quick add task that isn't in the cache
+#[derive(Debug, Clone)]
+pub enum QuickAddError {
+    UnknownProject(String),
+    UnknownLabel(String),
+}
+
+impl QuickAdd {
+    /// Split a task into its parts, `parser` is used to recognize the due date
+    pub fn parse(text : &str, parser : &DateParser) -> QuickAdd {
+        let mut quick = QuickAdd {
+            content: String::new(),
+            project: None,
+            labels: Vec::new(),
+            priority: None,
+            date_string: None,
+        };
+
+        let mut words = Vec::new();
+        for word in text.split_whitespace() {
+            if word.starts_with('#') && word.len() > 1 {
+                quick.project = Some(word[1..].to_string());
+            } else if word.starts_with('@') && word.len() > 1 {
+                quick.labels.push(word[1..].to_string());
+            } else if let Some(priority) = priority(word) {
+                quick.priority = Some(priority);
+            } else {
+                words.push(word);
+            }
+        }
+
+        let is_date = |d : &[&str]| {
+            // a lone abbreviation is only a date after "on", e.g. "Meet Bob on sat"
+            let meaningful : Vec<&str> = d.iter().cloned().filter(|w| !filler(w)).collect();
+            if meaningful.len() == 1 && !unambiguous(meaningful[0]) && !d[0].eq_ignore_ascii_case("on") {
+                return false;
+            }
+            // numbers are only a date when the rest makes that clear, e.g. "Update to 1.2" or "Meeting at 5" aren't dates
+            if d.iter().any(|w| w.contains(|c : char| c.is_digit(10))) && !d.iter().any(|w| clearly_date(w)) {
+                return false;
+            }
+            let s = d.join(" ");
+            parser.parse(&s).is_ok() || Recurrence::parse(&s).is_ok()
+        };
+
+        // prefer the longest date at the end of the task, then at the start, leaving at least one word of content
+        let suffix = (1..words.len()).find(|&i| is_date(&words[i..]));
+        let prefix = (1..words.len()).rev().find(|&i| is_date(&words[..i]));
+        let (content, date) = match (suffix, prefix) {
+            (Some(i), _) => (&words[..i], Some(&words[i..])),
+            (None, Some(i)) => (&words[i..], Some(&words[..i])),
+            (None, None) => (&words[..], None),
+        };
+
+        quick.content = content.join(" ");
+        quick.date_string = date.map(|d| d.join(" "));
+        quick
+    }
+
+    /// Create the command to add this task, looking up its project and labels in the cache
+    ///
+    /// Tasks without a project are added to the inbox.
+    pub fn command(&self, cache : &Cache) -> Result<command::item::Add, QuickAddError> {
+        let project = match self.project {
+            Some(ref name) => cache.projects.values()
+                .find(|p| p.name.to_lowercase() == name.to_lowercase())
+                .or_else(|| cache.get_project(name)),
+            None => cache.projects.values().find(|p| p.inbox),
+        };
+        let project = project.ok_or_else(|| QuickAddError::UnknownProject(self.project.clone().unwrap_or("Inbox".to_string())))?;
+
+        let mut add = command::item::Add::default()
+            .project_id(project.id)
+            .content(self.content.clone());
+
+        if let Some(priority) = self.priority {
+            add = add.priority(priority);
+        }
+
+        for name in self.labels.iter() {
+            match cache.labels.values().find(|l| l.name.to_lowercase() == name.to_lowercase()) {
+                Some(label) => add = add.labels(label.id),
+                None => return Err(QuickAddError::UnknownLabel(name.clone())),
+            }
+        }
+
+        if let Some(ref date) = self.date_string {
+            add = add.date_string(date.clone());
+        }
+        Ok(add)
+    }
+}
+
+/// Check if a word can be a date on its own
+///
+/// Abbreviations like "tom", "sun" or "sat" are left in the task's content unless they're part of a longer date
+/// (e.g. "next sat" or "on sat"), so tasks like "Call Tom" or "Sit in the sun" aren't given a due date.
+fn unambiguous(word : &str) -> bool {
+    const WORDS : [&'static str; 17] = ["today", "tomorrow", "yesterday", "weekend", "noon", "midnight",
+                                        "monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday",
+                                        "daily", "weekly", "monthly", "yearly"];
+    WORDS.contains(&word.to_lowercase().as_str()) || clearly_date(word)
+}
+
+/// Check if a word makes the numbers around it a date: a month's name, a unit ("3 days"), "every",
+/// a time with am/pm or a `:` ("5pm", "17:30"), or a numeric date with a year ("19/10/2018")
+fn clearly_date(word : &str) -> bool {
+    const WORDS : [&'static str; 35] = ["jan", "january", "feb", "february", "mar", "march", "apr", "april", "may",
+                                        "jun", "june", "jul", "july", "aug", "august", "sep", "sept", "september",
+                                        "oct", "october", "nov", "november", "dec", "december",
+                                        "day", "days", "week", "weeks", "month", "months", "year", "years",
+                                        "every", "ev", "every!"];
+    let word = word.to_lowercase();
+    let digits = |s : &str| !s.is_empty() && s.chars().all(|c| c.is_digit(10));
+    let clock = |s : &str| s.split(':').all(|p| digits(p));
+    let parts : Vec<&str> = word.split(|c| c == '/' || c == '-' || c == '.').collect();
+
+    WORDS.contains(&word.as_str())
+        || word == "am" || word == "pm"
+        || ((word.ends_with("am") || word.ends_with("pm")) && clock(&word[..word.len() - 2]))
+        || (word.contains(':') && clock(&word))
+        || (parts.len() == 3 && parts.iter().all(|p| digits(p)) && (parts[0].len() == 4 || parts[2].len() >= 2))
+}
+
+/// Words the date parser skips, e.g. "the" in "the 19th"
+fn filler(word : &str) -> bool {
+    ["at", "on", "of", "the"].contains(&word.to_lowercase().as_str())
+}
+
+/// "p1" to "p4"
+fn priority(word : &str) -> Option<Priority> {
+    let word = word.to_lowercase();
+    if word.len() == 2 && word.starts_with('p') {
+        word.parse().ok()
+    } else {
+        None
+    }
+}
+
+impl Cache {
+    /// Parse a task written in quick add syntax, and create the command to add it
+    pub fn quick_add(&self, text : &str) -> Result<command::item::Add, QuickAddError> {
+        QuickAdd::parse(text, &self.user.date_parser()).command(self)
+    }
+}
+
+impl fmt::Display for QuickAddError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QuickAddError::UnknownProject(name) => write!(f, "Unknown project \"{}\"", name),
+            QuickAddError::UnknownLabel(name) => write!(f, "Unknown label \"{}\"", name),
+        }
+    }
+}
+
+impl Error for QuickAddError {
+    fn description(&self) -> &'static str {
+        match self {
+            QuickAddError::UnknownProject(_) => "unknown project",
+            QuickAddError::UnknownLabel(_) => "unknown label",
+        }
+    }
+}
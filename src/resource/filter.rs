@@ -1,5 +1,7 @@
 use types::*;
+use types::serde_helpers;
 use command;
+use query::{Query, ParseError};
 use uuid::Uuid;
 
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
@@ -23,10 +25,12 @@ pub struct Filter {
     pub order : isize,
 
     /// whether this filter is marked as deleted
-    pub is_deleted : isize,
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_deleted : bool,
 
     /// whether this filter is marked as a favorite
-    pub is_favorite : isize,
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_favorite : bool,
 }
 
 impl Filter {
@@ -34,15 +38,14 @@ impl Filter {
         command::filter::Add::default()
     }
 
+    /// Start an update to this filter, only the fields that are set are changed
     pub fn update(&self) -> command::filter::Update {
-        command::filter::Update {
-            id: self.id,
-            item_order: self.order,
-            is_favorite: self.is_favorite,
-            name: self.name.clone(),
-            query: self.query.clone(),
-            color: self.color.clone(),
-        }
+        command::filter::Update::default().id(self.id)
+    }
+
+    /// Parse this filter's query
+    pub fn parse_query(&self) -> Result<Query, ParseError> {
+        Query::parse(&self.query)
     }
 
     pub fn delete(&self) -> command::filter::Delete {
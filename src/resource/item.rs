@@ -1,5 +1,7 @@
 use types::*;
+use types::serde_helpers;
 use command;
+use date_string::Recurrence;
 use uuid::Uuid;
 
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
@@ -27,8 +29,14 @@ pub struct Item {
     pub date_lang : Option<Language>,
 
     /// The date this TODO is due, or none 
+    ///
+    /// All-day dates are sent as 23:59:59 in the user's time zone, use `Item::due` to get the date with `all_day` set.
     pub due_date_utc : Option<Date>,
 
+    /// true if this item is due on a day, rather than at a specific time
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub all_day : bool,
+
     /// this item's importance
     pub priority : Priority,
 
@@ -41,8 +49,9 @@ pub struct Item {
     /// This item's position in the "Today" or "Next 7 Days" list, the smallest number should be at the top
     pub day_order : isize,
 
-    /// 0 if this item's children should be hidden
-    pub collapsed : isize,
+    /// true if this item's children are hidden
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub collapsed : bool,
 
     /// a list of label id's for the labels attached to this item
     pub labels : Vec<ID>,
@@ -53,20 +62,25 @@ pub struct Item {
     /// The user ID of the user who is assigned this task
     pub responsible_uid : Option<ID>,
 
-    /// 1 if this task has been completed
-    pub checked : isize,
+    /// true if this task has been completed
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub checked : bool,
 
-    // 1 if this item has been marked as as completely completed (all child tasks have also been completed)
-    pub in_history : isize,
+    /// true if this item has been marked as completely completed (all child tasks have also been completed)
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub in_history : bool,
 
-    // 1 if this item has been marked as deleted
-    pub is_deleted : isize,
+    /// true if this item has been marked as deleted
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_deleted : bool,
 
-    // 1 if this item has been marked as archived
-    pub is_archived : isize,
+    /// true if this item has been marked as archived
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_archived : bool,
 
-    // 1 if this item has been marked as a favorite
-    pub is_favorite : isize,
+    /// true if this item has been marked as a favorite
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_favorite : bool,
 
     /// used internally by Todoist, here for completeness 
     pub sync_id : Option<isize>,
@@ -80,22 +94,24 @@ impl Item {
         command::item::Add::default()
     }
 
+    /// Start an update to this item, only the fields that are set are changed
     pub fn update(&self) -> command::item::Update {
-        command::item::Update {
-            id: self.id,
-            item_order: self.item_order,
-            content: self.content.clone(),
-            assigned_by_uid: self.assigned_by_uid,
-            collapsed: self.collapsed,
-            date_string: self.date_string.clone(),
-            date_lang: self.date_lang.clone(),
-            day_order: self.day_order,
-            due_date_utc: self.due_date_utc.clone(),
-            indent: self.indent,
-            labels: self.labels.clone(),
-            priority: self.priority,
-            responsible_uid: self.responsible_uid,
-        }
+        command::item::Update::default().id(self.id)
+    }
+
+    /// The date this item is due, in the user's time zone
+    pub fn due(&self, tz : &TimeZoneInfo) -> Option<Date> {
+        let offset = tz.offset();
+        self.due_date_utc.as_ref().map(|d| if self.all_day {
+            d.to_all_day(&offset)
+        } else {
+            d.to_timezone(&offset)
+        })
+    }
+
+    /// Parse this item's date string if it's recurring (e.g. "every other day")
+    pub fn recurrence(&self) -> Option<Recurrence> {
+        self.date_string.as_ref().and_then(|s| Recurrence::parse(s).ok())
     }
 
     pub fn delete(&self) -> command::item::Delete {
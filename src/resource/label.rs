@@ -1,4 +1,5 @@
 use types::*;
+use types::serde_helpers;
 use command;
 use uuid::Uuid;
 
@@ -19,10 +20,12 @@ pub struct Label {
     /// This label's position in the label list, the smallest number should be at the top
     pub item_order : isize,
 
-    // 1 if this label has been marked as deleted
-    pub is_deleted : isize,
-    // 1 if this label has been marked as a favorite
-    pub is_favorite : isize,
+    /// true if this label has been marked as deleted
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_deleted : bool,
+    /// true if this label has been marked as a favorite
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_favorite : bool,
 }
 
 
@@ -31,14 +34,9 @@ impl Label {
         command::label::Add::default()
     }
 
+    /// Start an update to this label, only the fields that are set are changed
     pub fn update(&self) -> command::label::Update {
-        command::label::Update {
-            id: self.id,
-            item_order: self.item_order,
-            is_favorite: self.is_favorite,
-            name: self.name.clone(),
-            color: self.color.clone(),
-        }
+        command::label::Update::default().id(self.id)
     }
 
     pub fn delete(&self) -> command::label::Delete {
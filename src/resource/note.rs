@@ -2,6 +2,7 @@ use serde;
 
 use std::fmt;
 use types::*;
+use types::serde_helpers;
 use command;
 use uuid::Uuid;
 
@@ -53,13 +54,15 @@ pub struct Note {
     pub uids_to_notify : Vec<ID>,
 
     /// whether this note is marked as deleted
-    pub is_deleted : isize,
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_deleted : bool,
 
     /// whether this note has been marked as archived
-    pub is_archived : isize,
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_archived : bool,
 
     /// the date that this note was posted
-    pub posting : Date,
+    pub posting : Option<Date>,
 }
 
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
@@ -91,14 +94,39 @@ pub struct Attachment {
     /// large thumbnail
     pub tn_l : Option<Thumbnail>,
 }
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(default)]
 
+/// A note attached to a project instead of an item
+pub struct ProjectNote {
+    /// The note's unique ID
+    pub id : ID,
 
-pub struct ProjectNote(Note);
+    /// The ID of the note's poster
+    pub posted_uid : ID,
 
-impl Note {
-    fn project(self) -> ProjectNote {
-        ProjectNote(self)
-    }
+    /// The ID of the project the note is attached to
+    pub project_id : ID,
+
+    /// The note's text
+    pub content : String,
+
+    /// the file attached to this note
+    pub file_attachment : Option<Attachment>,
+
+    /// List of user ids to notify
+    pub uids_to_notify : Vec<ID>,
+
+    /// whether this note is marked as deleted
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_deleted : bool,
+
+    /// whether this note has been marked as archived
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_archived : bool,
+
+    /// the date that this note was posted
+    pub posted : Option<Date>,
 }
 
 impl<'de> Deserialize<'de> for Thumbnail {
@@ -151,17 +179,31 @@ impl Note {
         command::note::Add::default()
     }
 
+    /// Start an update to this note, only the fields that are set are changed
     pub fn update(&self) -> command::note::Update {
-        command::note::Update {
-            id: self.id,
-            content: self.content.clone(),
-            file_attachment: Some(self.file_attachment.clone()),
+        command::note::Update::default().id(self.id)
+    }
+
+    pub fn delete(&self) -> command::note::Delete {
+        command::note::Delete {
+            ids: vec![self.id]
         }
     }
+}
+impl ProjectNote {
+    /// Add a note to a project
+    pub fn add(project_id : ID) -> command::note::Add {
+        command::note::Add::default().project_id(project_id)
+    }
+
+    /// Start an update to this note, only the fields that are set are changed
+    pub fn update(&self) -> command::note::Update {
+        command::note::Update::default().id(self.id)
+    }
 
     pub fn delete(&self) -> command::note::Delete {
         command::note::Delete {
             ids: vec![self.id]
         }
     }
-}
\ No newline at end of file
+}
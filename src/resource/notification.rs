@@ -1,5 +1,9 @@
 use types::*;
+use types::serde_helpers;
+use command;
 use super::User;
+use serde::{Deserialize, Deserializer};
+use serde_json;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 
@@ -82,10 +86,12 @@ pub struct Reminder {
     pub name : Option<String>,
 
     /// the location's latitude
-    pub loc_lat : Option<isize>,
+    #[serde(deserialize_with = "serde_helpers::float_or_string")]
+    pub loc_lat : Option<f64>,
 
     /// the location longitude
-    pub loc_long : Option<isize>,
+    #[serde(deserialize_with = "serde_helpers::float_or_string")]
+    pub loc_long : Option<f64>,
     
     /// when the reminder should be triggered at the location
     pub loc_trigger : Option<NotificationTrigger>,
@@ -93,156 +99,312 @@ pub struct Reminder {
     /// the radius around the location that the reminder can be triggered in meters
     pub radius : Option<isize>,
 
-    // if this reminder has been marked as deleted
-    pub is_deleted : isize,
+    /// true if this reminder has been marked as deleted
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_deleted : bool,
 }
 
-#[derive(Serialize, Deserialize, Default, Debug)]
-#[serde(default)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+
+/// A location the user has set reminders at
+pub struct Location {
+    /// the location's name
+    pub name : String,
+
+    /// the location's latitude
+    #[serde(deserialize_with = "serde_helpers::required_float_or_string")]
+    pub lat : f64,
+
+    /// the location's longitude
+    #[serde(deserialize_with = "serde_helpers::required_float_or_string")]
+    pub long : f64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+
+/// A notification shown in Todoist's notification center
 pub struct LiveNotification {
     /// this notification's ID
     pub id : ID,
 
-    /// when this live notification was added (in unix time)
-    pub add : i64,
+    /// when this live notification was created (in unix time)
+    #[serde(default)]
+    pub created : i64,
 
-    /// the user who Add this notification
+    /// the user who caused this notification
+    #[serde(default)]
     pub from_uid : ID,
 
     /// unique key for this notification
+    #[serde(default)]
     pub notification_key : String,
 
     /// notification sequence number
+    #[serde(default)]
     pub seq_no : isize,
 
-    /// whether this notification has been read
-    pub is_unread : isize,
-
-    // -----------------------------------
-    // INVITATION PROPERTIES
-    // -----------------------------------
-    
-    /// the user who is being invited
-    pub from_user : Option<User>,
+    /// true if this notification hasn't been read
+    #[serde(default, deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_unread : bool,
 
-    /// the project name
-    pub project_name : Option<String>,
-
-    /// the invitations ID
-    pub invitation_id : Option<ID>,
-
-    /// the invitation secret, used for accepting/rejecting it
-    pub invitation_secret : Option<String>,
-
-    // -----------------------------------
-    // SHARE INVITATION SENT PROPERTIES
-    // -----------------------------------
+    /// what this notification is about
+    #[serde(flatten, deserialize_with = "lenient_kind")]
+    pub kind : LiveNotificationKind,
+}
 
-    /// the invitation state
-    pub state : Option<String>, // TODO make this an enum
+/// The kinds of live notifications, and the properties specific to each
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "notification_type")]
+pub enum LiveNotificationKind {
+    /// the user was invited to a project
+    #[serde(rename = "share_invitation_sent")]
+    ShareInvitationSent {
+        /// the user who sent the invitation
+        from_user : User,
 
-    // -----------------------------------
-    // USER REMOVED FROM PROJECT PROPERTIES
-    // -----------------------------------
+        /// the project's name
+        project_name : String,
 
-    /// the user removed (name)
-    pub removed_name : Option<String>,
+        /// the invitation's ID
+        invitation_id : ID,
 
-    /// the user removed (uid)
-    pub removed_uid : Option<ID>,
+        /// the invitation secret, used for accepting/rejecting it
+        invitation_secret : String,
 
-    // -----------------------------------
-    // BUSINESS ACCOUNT PROPERTIES
-    // -----------------------------------
+        /// whether the invitation has been accepted or rejected
+        state : InvitationState,
+    },
 
-    /// The number of users in the business
-    pub quantity : Option<isize>,
-    
-    /// the tariff plan name, business_monthly or business_yearly
-    pub plan : Option<String>, // TODO make this an enum
-    
-    /// when the business account will be disabled (unix time)
-    pub active_until : Option<i64>,
+    /// an invitation the user sent was accepted
+    #[serde(rename = "share_invitation_accepted")]
+    ShareInvitationAccepted {
+        /// the shared project's ID
+        project_id : ID,
+    },
 
-    // -----------------------------------
-    // BUSINESS PAYMENT FAILED PROPERTIES
-    // -----------------------------------
+    /// an invitation the user sent was rejected
+    #[serde(rename = "share_invitation_rejected")]
+    ShareInvitationRejected {
+        /// the shared project's ID
+        project_id : ID,
 
-    /// amount due in hundredths of one unit of currency
-    pub amount_due : Option<isize>,
+        /// the email the invitation was sent to
+        #[serde(default)]
+        reject_email : Option<String>,
+    },
 
-    /// the number of previous payment attempts
-    pub attempt_count : Option<isize>,
+    /// a user was removed from a shared project
+    #[serde(rename = "user_removed_from_project")]
+    UserRemovedFromProject {
+        /// the project's ID
+        project_id : ID,
 
-    /// currency, three letter ISO code
-    pub currency : Option<String>,
+        /// the removed user's name
+        removed_name : String,
 
-    /// invoice description
-    pub description : Option<String>,
+        /// the removed user's ID
+        removed_uid : ID,
+    },
 
-    /// next payment attempt date (in unix time)
-    pub next_payment_attempt : Option<i64>,
+    /// an item was assigned to the user
+    #[serde(rename = "item_assigned")]
+    ItemAssigned {
+        /// the assigned item's ID
+        item_id : ID,
 
-    // -----------------------------------
-    // BUSINESS INVITATION PROPERTIES
-    // -----------------------------------
+        /// the ID of the item's project
+        project_id : ID,
 
-    /// the invitation's message
-    pub invitation_message : Option<String>,
+        /// the user the item was assigned to
+        responsible_uid : ID,
+    },
 
-    /// the business account's name
-    pub account_name      : Option<String>,
-}
+    /// an item assigned to the user was completed
+    #[serde(rename = "item_completed")]
+    ItemCompleted {
+        /// the completed item's ID
+        item_id : ID,
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub enum LiveNotificationType {
-    #[serde(rename = "share_invitation_sent")]
-    ShareInvitationSent,
+        /// the completed item's content
+        item_content : String,
 
-    #[serde(rename = "share_invitation_accepted")]
-    ShareInvitationAccepted,
+        /// the user the item was assigned to
+        responsible_uid : ID,
+    },
 
-    #[serde(rename = "share_invitation_rejected")]
-    ShareInvitationRejected,
+    /// an item assigned to the user was uncompleted
+    #[serde(rename = "item_uncompleted")]
+    ItemUncompleted {
+        /// the uncompleted item's ID
+        item_id : ID,
 
-    #[serde(rename = "user_removed_from_project")]
-    UserRemovedFromProject,
+        /// the uncompleted item's content
+        item_content : String,
 
-    #[serde(rename = "item_assigned")]
-    ItemAssigned,
+        /// the user the item was assigned to
+        responsible_uid : ID,
+    },
 
-    #[serde(rename = "item_completed")]
-    ItemCompleted,
+    /// a note was added to an item in a shared project
+    #[serde(rename = "note_added")]
+    NoteAdded {
+        /// the new note's ID
+        note_id : ID,
 
-    #[serde(rename = "item_uncompleted")]
-    ItemUncompleted,
+        /// the ID of the note's item
+        item_id : ID,
 
-    #[serde(rename = "note_added")]
-    NoteAdded,
+        /// the ID of the note's project
+        project_id : ID,
+    },
 
+    /// the user's business account's policy doesn't allow them to accept an invitation
     #[serde(rename = "biz_policy_disallowed_invitation")]
-    BusinessPolicyDisallowedInvitation,
+    BusinessPolicyDisallowedInvitation {
+        /// the project the user was invited to
+        project_id : ID,
+
+        /// the user who sent the invitation
+        from_user : User,
+    },
 
+    /// an invitation the user sent was rejected by the invitee's business account policy
     #[serde(rename = "biz_policy_rejected_invitation")]
-    BusinessPolicyRejectInvitation,
+    BusinessPolicyRejectedInvitation {
+        /// the user the invitation was sent to
+        from_user : User,
+    },
 
+    /// the business account's trial is ending soon
     #[serde(rename = "biz_trial_will_end")]
-    BusinessTrialWillEnd,
+    BusinessTrialWillEnd {
+        /// the number of users in the business
+        quantity : isize,
 
+        /// the tariff plan
+        plan : BusinessPlan,
+
+        /// when the business account will be disabled (unix time)
+        active_until : i64,
+    },
+
+    /// a payment for the business account failed
     #[serde(rename = "biz_payment_failed")]
-    BusinessPaymentFailed,
+    BusinessPaymentFailed {
+        /// amount due in hundredths of one unit of currency
+        amount_due : isize,
+
+        /// the number of previous payment attempts
+        attempt_count : isize,
 
+        /// currency, three letter ISO code
+        currency : String,
+
+        /// invoice description
+        description : String,
+
+        /// next payment attempt date (in unix time)
+        next_payment_attempt : i64,
+    },
+
+    /// the business account was disabled
     #[serde(rename = "biz_account_disabled")]
-    BusinessAccountDisabled,
+    BusinessAccountDisabled {
+        /// the number of users in the business
+        quantity : isize,
+
+        /// the tariff plan
+        plan : BusinessPlan,
+
+        /// when the business account was disabled (unix time)
+        active_until : i64,
+    },
 
+    /// the user was invited to a business account
     #[serde(rename = "biz_invitation_added")]
-    BusinessInvitationAdedd,
+    BusinessInvitationAdded {
+        /// the user who sent the invitation
+        from_user : User,
 
+        /// the business account's name
+        account_name : String,
+
+        /// the invitation's ID
+        invitation_id : ID,
+
+        /// the invitation secret, used for accepting/rejecting it
+        invitation_secret : String,
+
+        /// the invitation's message
+        #[serde(default)]
+        invitation_message : Option<String>,
+
+        /// whether the invitation has been accepted or rejected
+        state : InvitationState,
+    },
+
+    /// an invitation to the user's business account was accepted
     #[serde(rename = "biz_invitation_accepted")]
-    BusinessInvitationAccepted,
+    BusinessInvitationAccepted {
+        /// the user who accepted the invitation
+        from_user : User,
+
+        /// the business account's name
+        account_name : String,
+    },
 
+    /// an invitation to the user's business account was rejected
     #[serde(rename = "biz_invitation_rejected")]
-    BusinessInvitationRejected,
+    BusinessInvitationRejected {
+        /// the user who rejected the invitation
+        from_user : User,
+
+        /// the business account's name
+        account_name : String,
+
+        /// the invitation's message
+        #[serde(default)]
+        invitation_message : Option<String>,
+    },
+
+    /// a notification this library doesn't know about, or one with missing properties
+    #[serde(rename = "unknown")]
+    Unknown,
+}
+
+/// Deserialize a notification's kind, falling back to `LiveNotificationKind::Unknown`
+/// so that a single unexpected notification doesn't break the whole sync
+fn lenient_kind<'de, D : Deserializer<'de>>(deserializer : D) -> Result<LiveNotificationKind, D::Error> {
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(LiveNotificationKind::deserialize(value).unwrap_or(LiveNotificationKind::Unknown))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+
+/// The state of a project or business invitation
+pub enum InvitationState {
+    #[serde(rename = "invited")]
+    Invited,
+
+    #[serde(rename = "accepted")]
+    Accepted,
+
+    #[serde(rename = "rejected")]
+    Rejected,
+
+    #[serde(rename = "deleted")]
+    Deleted,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+
+/// A business account's tariff plan
+pub enum BusinessPlan {
+    #[serde(rename = "business_monthly")]
+    Monthly,
+
+    #[serde(rename = "business_yearly")]
+    Yearly,
 }
 
 impl Default for NotificationService {
@@ -255,4 +417,100 @@ impl Default for NotificationType {
     fn default() -> NotificationType {
         NotificationType::None
     }
-}
\ No newline at end of file
+}
+
+impl Reminder {
+    pub fn add() -> command::reminder::Add {
+        command::reminder::Add::default()
+    }
+
+    /// Start an update to this reminder, only the fields that are set are changed
+    pub fn update(&self) -> command::reminder::Update {
+        command::reminder::Update::default().id(self.id)
+    }
+
+    pub fn delete(&self) -> command::reminder::Delete {
+        command::reminder::Delete {
+            id: self.id
+        }
+    }
+}
+
+impl LiveNotification {
+    pub fn mark_read(&self) -> command::live_notification::MarkRead {
+        command::live_notification::MarkRead {
+            id: self.id
+        }
+    }
+
+    pub fn mark_unread(&self) -> command::live_notification::MarkUnread {
+        command::live_notification::MarkUnread {
+            id: self.id
+        }
+    }
+
+    /// Mark this notification, and every notification before it, as read
+    pub fn set_last_read(&self) -> command::live_notification::SetLastRead {
+        command::live_notification::SetLastRead {
+            id: self.id
+        }
+    }
+
+    pub fn mark_all_read() -> command::live_notification::MarkReadAll {
+        command::live_notification::MarkReadAll::default()
+    }
+
+    /// Accept the project or business invitation this notification is about, if it's about one
+    pub fn accept(&self) -> Option<command::CommandArgs> {
+        match self.kind {
+            LiveNotificationKind::ShareInvitationSent { invitation_id, ref invitation_secret, .. } => {
+                Some(command::invitation::Accept {
+                    invitation_id: invitation_id,
+                    invitation_secret: invitation_secret.clone(),
+                }.into())
+            },
+            LiveNotificationKind::BusinessInvitationAdded { invitation_id, ref invitation_secret, .. } => {
+                Some(command::invitation::BusinessAccept {
+                    invitation_id: invitation_id,
+                    invitation_secret: invitation_secret.clone(),
+                }.into())
+            },
+            _ => None,
+        }
+    }
+
+    /// Reject the project or business invitation this notification is about, if it's about one
+    pub fn reject(&self) -> Option<command::CommandArgs> {
+        match self.kind {
+            LiveNotificationKind::ShareInvitationSent { invitation_id, ref invitation_secret, .. } => {
+                Some(command::invitation::Reject {
+                    invitation_id: invitation_id,
+                    invitation_secret: invitation_secret.clone(),
+                }.into())
+            },
+            LiveNotificationKind::BusinessInvitationAdded { invitation_id, ref invitation_secret, .. } => {
+                Some(command::invitation::BusinessReject {
+                    invitation_id: invitation_id,
+                    invitation_secret: invitation_secret.clone(),
+                }.into())
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Location {
+    /// Create a reminder that's triggered when entering or leaving this location
+    ///
+    /// `radius` is the distance from the location, in meters, that triggers the reminder.
+    pub fn reminder(&self, item_id : ID, trigger : NotificationTrigger, radius : isize) -> command::reminder::Add {
+        Reminder::add()
+            .item_id(item_id)
+            .typ(NotificationType::Location)
+            .name(self.name.clone())
+            .loc_lat(self.lat)
+            .loc_long(self.long)
+            .loc_trigger(trigger)
+            .radius(Some(radius))
+    }
+}
@@ -1,5 +1,6 @@
 //! Project related structures
 use types::*;
+use types::serde_helpers;
 use uuid::Uuid;
 
 use command;
@@ -24,19 +25,23 @@ pub struct Project {
     /// This project's position in the project list, the smallest number should be at the top
     pub item_order : isize,
 
-    /// Whether this project's child's project's children are visible
-    pub collapsed : isize,
+    /// true if this project's sub-projects are hidden
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub collapsed : bool,
 
     pub shared : bool,
 
-    // 1 if this project has been marked as deleted
-    pub is_deleted : isize,
+    /// true if this project has been marked as deleted
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_deleted : bool,
 
-    // 1 if this project has been marked as archived
-    pub is_archived :isize,
+    /// true if this project has been marked as archived
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_archived : bool,
 
-    // 1 if this project has been marked as a favorite
-    pub is_favorite : isize,
+    /// true if this project has been marked as a favorite
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_favorite : bool,
 
     /// True if this project is in the user's inbox
     pub inbox : bool,
@@ -50,16 +55,9 @@ impl Project {
         command::project::Add::default()
     }
 
+    /// Start an update to this project, only the fields that are set are changed
     pub fn update(&self) -> command::project::Update {
-        command::project::Update {
-            id: self.id,
-            item_order: self.item_order,
-            is_favorite: self.is_favorite,
-            name: self.name.clone(),
-            color: self.color.clone(),
-            indent: self.indent,
-            collapsed: self.collapsed,
-        }
+        command::project::Update::default().id(self.id)
     }
 
     pub fn delete(&self) -> command::project::Delete {
@@ -67,4 +65,16 @@ impl Project {
             ids: vec![self.id]
         }
     }
-}
\ No newline at end of file
+
+    pub fn archive(&self) -> command::project::Archive {
+        command::project::Archive {
+            ids: vec![self.id]
+        }
+    }
+
+    pub fn unarchive(&self) -> command::project::Unarchive {
+        command::project::Unarchive {
+            ids: vec![self.id]
+        }
+    }
+}
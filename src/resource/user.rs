@@ -1,4 +1,5 @@
 use types::*;
+use command;
 use super::NotificationService;
 
 #[derive(Serialize, Deserialize, Default, Debug)]
@@ -12,6 +13,9 @@ pub struct Collaborator {
     /// the collaborator's email
     pub email : String,
 
+    /// the collaborator's full name
+    pub full_name : String,
+
     /// the collaborator's timezone,
     pub timezone : String,
 
@@ -117,5 +121,34 @@ pub struct User {
     pub features : Option<()>,
 
     /// When the user joined
-    pub join_date : Date,
+    pub join_date : Option<Date>,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+
+/// How the user is notified about one kind of event (e.g. "item_completed")
+pub struct NotificationSetting {
+    /// whether a push notification is sent
+    pub notify_push : bool,
+
+    /// whether an email is sent
+    pub notify_email : bool,
+}
+
+impl User {
+    /// Start an update to this user's settings, only the fields that are set are changed
+    pub fn update(&self) -> command::user::Update {
+        command::user::Update::default()
+    }
+
+    /// Turn notifications about an event (e.g. "item_completed") on or off for a service (email or push)
+    pub fn update_notification_setting<S : AsRef<str>>(notification_type : S, service : NotificationService, notify : bool)
+        -> command::user::UpdateNotificationSetting {
+        command::user::UpdateNotificationSetting {
+            notification_type: notification_type.as_ref().to_owned(),
+            service: service,
+            dont_notify: !notify,
+        }
+    }
 }
@@ -0,0 +1,276 @@
+//! Productivity stats
+//!
+//! `User` only carries the user's karma and completed counts, `Client::productivity_stats`
+//! gets the full breakdown: items completed each day and week, goals, streaks and why karma changed.
+use types::*;
+use types::serde_helpers;
+
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use serde::de;
+use chrono::NaiveDate;
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The user's productivity stats
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct ProductivityStats {
+    /// the user's karma score
+    pub karma : f64,
+
+    /// the user's karma trend, e.g. up
+    pub karma_trend : String,
+
+    /// the karma gained or lost in the last update
+    pub karma_last_update : f64,
+
+    /// the total number of completed tasks
+    pub completed_count : usize,
+
+    /// items completed on each of the last few days, most recent first
+    pub days_items : Vec<DayItems>,
+
+    /// items completed in each of the last few weeks, most recent first
+    pub week_items : Vec<WeekItems>,
+
+    /// recent changes to the user's karma, most recent first
+    pub karma_update_reasons : Vec<KarmaUpdate>,
+
+    pub goals : Goals,
+}
+
+/// The items completed on a day
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DayItems {
+    pub date : NaiveDate,
+
+    /// the number of items completed in each project
+    pub items : Vec<ProjectCount>,
+
+    pub total_completed : usize,
+}
+
+/// The items completed in a week
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WeekItems {
+    /// the first and last day of the week
+    #[serde(rename = "date")]
+    pub week : DateRange,
+
+    /// the number of items completed in each project
+    pub items : Vec<ProjectCount>,
+
+    pub total_completed : usize,
+}
+
+/// A range of days, sent as `2018-10-15/2018-10-21`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    pub start : NaiveDate,
+    pub end : NaiveDate,
+}
+
+/// The number of items completed in a project
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProjectCount {
+    #[serde(rename = "id", deserialize_with = "serde_helpers::id_or_string")]
+    pub project_id : ID,
+
+    pub completed : usize,
+}
+
+/// A change to the user's karma
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct KarmaUpdate {
+    /// when the karma was updated
+    pub time : Date,
+
+    /// the karma score after the update
+    pub new_karma : f64,
+
+    /// the karma gained
+    pub positive_karma : f64,
+
+    /// the karma lost
+    pub negative_karma : f64,
+
+    #[serde(default)]
+    pub positive_karma_reasons : Vec<KarmaReason>,
+
+    #[serde(default)]
+    pub negative_karma_reasons : Vec<KarmaReason>,
+}
+
+/// Why the user's karma changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KarmaReason {
+    AddedTasks,
+    CompletedTasks,
+    AdvancedFeatures,
+    UsingTodoist,
+    JoinedBeta,
+    UsedSupport,
+    Premium,
+    GettingStarted,
+    DailyGoal,
+    WeeklyGoal,
+    OverdueTasks,
+    Inactive,
+    Other(u64),
+}
+
+/// The user's daily and weekly goals, and their progress towards them
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct Goals {
+    /// the number of items the user wants to complete each day
+    pub daily_goal : usize,
+
+    /// the number of items the user wants to complete each week
+    pub weekly_goal : usize,
+
+    /// days that don't count towards goals, from 1 (Monday) to 7 (Sunday)
+    pub ignore_days : Vec<u8>,
+
+    /// true if streaks are paused
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub vacation_mode : bool,
+
+    /// true if the user has disabled karma
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub karma_disabled : bool,
+
+    pub current_daily_streak : Streak,
+    pub max_daily_streak : Streak,
+    pub current_weekly_streak : Streak,
+    pub max_weekly_streak : Streak,
+}
+
+/// A run of days or weeks where a goal was reached
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct Streak {
+    /// the number of days or weeks in the streak
+    pub count : usize,
+
+    /// the streak's first day, none if there's no streak
+    #[serde(deserialize_with = "serde_helpers::optional_date")]
+    pub start : Option<NaiveDate>,
+
+    /// the streak's last day, none if there's no streak
+    #[serde(deserialize_with = "serde_helpers::optional_date")]
+    pub end : Option<NaiveDate>,
+}
+
+impl KarmaReason {
+    /// Get the reason with the number Todoist uses for it
+    pub fn from_api(id : u64) -> KarmaReason {
+        match id {
+            1 => KarmaReason::AddedTasks,
+            2 => KarmaReason::CompletedTasks,
+            3 => KarmaReason::AdvancedFeatures,
+            4 => KarmaReason::UsingTodoist,
+            5 => KarmaReason::JoinedBeta,
+            6 => KarmaReason::UsedSupport,
+            7 => KarmaReason::Premium,
+            8 => KarmaReason::GettingStarted,
+            9 => KarmaReason::DailyGoal,
+            10 => KarmaReason::WeeklyGoal,
+            50 => KarmaReason::OverdueTasks,
+            52 => KarmaReason::Inactive,
+            n => KarmaReason::Other(n),
+        }
+    }
+
+    /// The number Todoist uses for this reason
+    pub fn api(&self) -> u64 {
+        match self {
+            KarmaReason::AddedTasks => 1,
+            KarmaReason::CompletedTasks => 2,
+            KarmaReason::AdvancedFeatures => 3,
+            KarmaReason::UsingTodoist => 4,
+            KarmaReason::JoinedBeta => 5,
+            KarmaReason::UsedSupport => 6,
+            KarmaReason::Premium => 7,
+            KarmaReason::GettingStarted => 8,
+            KarmaReason::DailyGoal => 9,
+            KarmaReason::WeeklyGoal => 10,
+            KarmaReason::OverdueTasks => 50,
+            KarmaReason::Inactive => 52,
+            KarmaReason::Other(n) => *n,
+        }
+    }
+
+    /// A short description of this reason, e.g. "completed tasks"
+    pub fn description(&self) -> &'static str {
+        match self {
+            KarmaReason::AddedTasks => "added tasks",
+            KarmaReason::CompletedTasks => "completed tasks",
+            KarmaReason::AdvancedFeatures => "used advanced features",
+            KarmaReason::UsingTodoist => "used Todoist",
+            KarmaReason::JoinedBeta => "signed up for the beta",
+            KarmaReason::UsedSupport => "used the support section",
+            KarmaReason::Premium => "used Todoist Premium",
+            KarmaReason::GettingStarted => "completed a getting started guide task",
+            KarmaReason::DailyGoal => "reached the daily goal",
+            KarmaReason::WeeklyGoal => "reached the weekly goal",
+            KarmaReason::OverdueTasks => "had tasks over 4 days overdue",
+            KarmaReason::Inactive => "didn't log in for more than 4 weeks",
+            KarmaReason::Other(_) => "unknown reason",
+        }
+    }
+}
+
+impl fmt::Display for KarmaReason {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+impl Serialize for KarmaReason {
+    fn serialize<S : Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.api())
+    }
+}
+
+impl<'de> Deserialize<'de> for KarmaReason {
+    fn deserialize<D : Deserializer<'de>>(deserializer: D) -> Result<KarmaReason, D::Error> {
+        u64::deserialize(deserializer).map(KarmaReason::from_api)
+    }
+}
+
+impl fmt::Display for DateRange {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.start.format("%Y-%m-%d"), self.end.format("%Y-%m-%d"))
+    }
+}
+
+impl FromStr for DateRange {
+    type Err = String;
+
+    fn from_str(s : &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, '/');
+        let mut next = || parts.next()
+            .ok_or_else(|| format!("invalid date range \"{}\"", s))
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").map_err(|e| e.to_string()));
+
+        Ok(DateRange {
+            start: next()?,
+            end: next()?,
+        })
+    }
+}
+
+impl Serialize for DateRange {
+    fn serialize<S : Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for DateRange {
+    fn deserialize<D : Deserializer<'de>>(deserializer: D) -> Result<DateRange, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,343 @@
+//! Project templates
+//!
+//! Templates are CSV files in the format Todoist uses to import and export projects:
+//! a `TYPE,CONTENT,PRIORITY,INDENT,AUTHOR,RESPONSIBLE,DATE,DATE_LANG,TIMEZONE` header,
+//! followed by one row for each task, and a row after it for each of the task's notes.
+//! Priorities are numbered as they are in the UI, from 1 (urgent) to 4 (normal).
+//! Labels are written in the task's content, e.g. `Buy milk @store`.
+use types::*;
+use cache::Cache;
+use resource::{Item, Note, Project};
+use Transaction;
+
+use std::error::Error;
+use std::fmt;
+
+/// The columns of a template, in the order Todoist writes them
+const COLUMNS : [&'static str; 9] = ["TYPE", "CONTENT", "PRIORITY", "INDENT", "AUTHOR", "RESPONSIBLE", "DATE", "DATE_LANG", "TIMEZONE"];
+
+/// A project template
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Template {
+    pub rows : Vec<TemplateRow>,
+}
+
+/// A task or note in a template
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateRow {
+    Task(TemplateTask),
+
+    /// A note attached to the task before it
+    Note(String),
+}
+
+/// A task in a template
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateTask {
+    /// The task's text, without its labels
+    pub content : String,
+
+    pub priority : Priority,
+
+    /// The task's indent, from 1 to 4
+    pub indent : u8,
+
+    /// The names of the task's labels
+    pub labels : Vec<String>,
+
+    pub date_string : Option<String>,
+    pub date_lang : Option<Language>,
+}
+
+/// A template that couldn't be parsed
+#[derive(Debug, Clone)]
+pub struct TemplateError {
+    /// the line the error is on, starting from 1
+    pub line : usize,
+    pub message : String,
+}
+
+impl Template {
+    /// Create a template from a cached project's items, and their notes
+    ///
+    /// Completed and deleted items are left out.
+    pub fn from_project(cache : &Cache, project : &Project) -> Template {
+        let mut items : Vec<&Item> = cache.items.values()
+            .filter(|item| item.project_id == project.id && !item.checked && !item.is_deleted)
+            .collect();
+        items.sort_by_key(|item| item.item_order);
+
+        let mut rows = Vec::new();
+        for item in items {
+            rows.push(TemplateRow::Task(TemplateTask {
+                content: item.content.clone().unwrap_or_default(),
+                priority: item.priority,
+                indent: item.indent,
+                labels: item.labels.iter()
+                    .filter_map(|id| cache.labels.get(id))
+                    .map(|label| label.name.clone())
+                    .collect(),
+                date_string: item.date_string.clone(),
+                date_lang: item.date_lang.clone(),
+            }));
+
+            rows.extend(cache.notes_for_item(item.id).into_iter()
+                .map(|note : &Note| TemplateRow::Note(note.content.clone())));
+        }
+
+        Template { rows: rows }
+    }
+
+    /// Parse a template from CSV
+    pub fn parse(csv : &str) -> Result<Template, TemplateError> {
+        let mut records = parse_csv(csv)?.into_iter()
+            .filter(|&(_, ref fields)| fields.iter().any(|f| !f.is_empty()));
+
+        let header = match records.next() {
+            Some((_, fields)) => fields,
+            None => return Ok(Template::default()),
+        };
+        let column = |name : &str| header.iter().position(|h| h.trim().eq_ignore_ascii_case(name));
+        let (typ, content) = match (column("TYPE"), column("CONTENT")) {
+            (Some(typ), Some(content)) => (typ, content),
+            _ => return Err(TemplateError::new(1, "expected a TYPE and CONTENT column")),
+        };
+        let (priority, indent, date, date_lang) = (column("PRIORITY"), column("INDENT"), column("DATE"), column("DATE_LANG"));
+
+        let mut rows = Vec::new();
+        for (line, fields) in records {
+            let field = |i : Option<usize>| i.and_then(|i| fields.get(i))
+                .map(|f| f.trim())
+                .filter(|f| !f.is_empty());
+
+            match field(Some(typ)).map(|t| t.to_lowercase()) {
+                Some(ref t) if t == "task" => {
+                    let priority = match field(priority) {
+                        Some(p) => p.parse::<Priority>().ok()
+                            .ok_or_else(|| TemplateError::new(line, &format!("invalid priority \"{}\"", p)))?,
+                        None => Priority::P4,
+                    };
+                    let indent = match field(indent) {
+                        Some(i) => i.parse().ok().filter(|i| *i >= 1 && *i <= 4)
+                            .ok_or_else(|| TemplateError::new(line, &format!("invalid indent \"{}\"", i)))?,
+                        None => 1,
+                    };
+
+                    let (content, labels) = split_labels(field(Some(content)).unwrap_or(""));
+                    rows.push(TemplateRow::Task(TemplateTask {
+                        content: content,
+                        priority: priority,
+                        indent: indent,
+                        labels: labels,
+                        date_string: field(date).map(|d| d.to_string()),
+                        date_lang: field(date_lang).and_then(|l| l.parse().ok()),
+                    }));
+                },
+                Some(ref t) if t == "note" => {
+                    if rows.is_empty() {
+                        return Err(TemplateError::new(line, "a note must come after a task"));
+                    }
+                    rows.push(TemplateRow::Note(field(Some(content)).unwrap_or("").to_string()));
+                },
+                Some(t) => return Err(TemplateError::new(line, &format!("unknown row type \"{}\"", t))),
+                None => return Err(TemplateError::new(line, "missing row type")),
+            }
+        }
+
+        Ok(Template { rows: rows })
+    }
+
+    /// Write this template as CSV
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        write_record(&mut csv, &COLUMNS.iter().map(|c| c.to_string()).collect::<Vec<_>>());
+
+        for row in self.rows.iter() {
+            let record = match row {
+                TemplateRow::Task(task) => {
+                    let mut content = task.content.clone();
+                    for label in task.labels.iter() {
+                        content.push_str(" @");
+                        content.push_str(label);
+                    }
+
+                    vec!["task".to_string(),
+                         content,
+                         task.priority.label()[1..].to_string(),
+                         task.indent.max(1).to_string(),
+                         String::new(),
+                         String::new(),
+                         task.date_string.clone().unwrap_or_default(),
+                         task.date_lang.as_ref().map(|l| l.code().to_string()).unwrap_or_default(),
+                         String::new()]
+                },
+                TemplateRow::Note(content) => {
+                    let mut record = vec![String::new(); COLUMNS.len()];
+                    record[0] = "note".to_string();
+                    record[1] = content.clone();
+                    record
+                },
+            };
+            write_record(&mut csv, &record);
+        }
+        csv
+    }
+
+    /// Add commands to a transaction that import this template into a project, after its existing items
+    ///
+    /// The project can be one that's created earlier in the same transaction.
+    /// Labels are looked up in the cache by name, labels that don't exist are left in the task's content.
+    pub fn import<P : Into<IdRef>>(&self, tx : &mut Transaction, cache : &Cache, project : P) {
+        let project = project.into();
+        let mut item_order = match project {
+            IdRef::Id(id) => cache.items.values()
+                .filter(|item| item.project_id == id)
+                .map(|item| item.item_order)
+                .max()
+                .unwrap_or(0),
+            IdRef::Temp(_) => 0,
+        };
+
+        let mut last_item = None;
+        for row in self.rows.iter() {
+            match row {
+                TemplateRow::Task(task) => {
+                    item_order += 1;
+                    let mut content = task.content.clone();
+                    let mut add = Item::add()
+                        .project_id(project)
+                        .priority(task.priority)
+                        .indent(task.indent)
+                        .item_order(item_order);
+
+                    for name in task.labels.iter() {
+                        match cache.labels.values().find(|l| l.name.to_lowercase() == name.to_lowercase()) {
+                            Some(label) => add = add.labels(label.id),
+                            None => {
+                                content.push_str(" @");
+                                content.push_str(name);
+                            },
+                        }
+                    }
+
+                    add = add.content(content);
+                    if let Some(ref date) = task.date_string {
+                        add = add.date_string(date.clone());
+                    }
+                    if let Some(ref lang) = task.date_lang {
+                        add = add.date_lang(lang.clone());
+                    }
+                    last_item = Some(tx.create(add));
+                },
+                TemplateRow::Note(content) => {
+                    if let Some(item) = last_item {
+                        tx.exec(Note::add()
+                            .item_id(item)
+                            .content(content.clone()));
+                    }
+                },
+            }
+        }
+    }
+
+    /// Add commands to a transaction that create a new project, and import this template into it
+    pub fn import_new(&self, tx : &mut Transaction, cache : &Cache, name : &str) -> IdRef {
+        let item_order = cache.projects.values()
+            .map(|p| p.item_order)
+            .max()
+            .unwrap_or(0);
+
+        let project = tx.create(Project::add()
+            .name(name)
+            .indent(1)
+            .item_order(item_order + 1));
+        self.import(tx, cache, project);
+        project
+    }
+}
+
+/// Split the labels (`@name`) out of a task's content
+fn split_labels(content : &str) -> (String, Vec<String>) {
+    let (labels, words) : (Vec<&str>, Vec<&str>) = content.split_whitespace()
+        .partition(|w| w.starts_with('@') && w.len() > 1);
+    (words.join(" "), labels.iter().map(|l| l[1..].to_string()).collect())
+}
+
+/// Parse CSV into records, along with the line each record starts on
+fn parse_csv(csv : &str) -> Result<Vec<(usize, Vec<String>)>, TemplateError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut start = 1;
+
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            },
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => record.push(field.split_off(0)),
+            '\r' if !quoted => (),
+            '\n' if !quoted => {
+                record.push(field.split_off(0));
+                records.push((start, record.split_off(0)));
+                line += 1;
+                start = line;
+            },
+            c => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            },
+        }
+    }
+
+    if quoted {
+        return Err(TemplateError::new(start, "unterminated quote"));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((start, record));
+    }
+    Ok(records)
+}
+
+/// Write a CSV record, quoting fields when necessary
+fn write_record(csv : &mut String, fields : &[String]) {
+    let fields : Vec<String> = fields.iter()
+        .map(|f| if f.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r') {
+            format!("\"{}\"", f.replace("\"", "\"\""))
+        } else {
+            f.clone()
+        })
+        .collect();
+    csv.push_str(&fields.join(","));
+    csv.push('\n');
+}
+
+impl TemplateError {
+    fn new(line : usize, message : &str) -> TemplateError {
+        TemplateError {
+            line: line,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for TemplateError {
+    fn description(&self) -> &'static str {
+        "invalid template"
+    }
+}
@@ -1,5 +1,4 @@
 use serde::{Serialize, Deserialize};
-use std::string::ToString;
 use std::error::Error;
 use std::str::FromStr;
 
@@ -7,13 +6,15 @@ use serde::ser;
 use serde::de;
 
 use std::fmt;
-use std::mem;
 
 /// Colors can be used to organize some Todoist types, like projects and tasks.
 ///
 /// Each color is mapped to a number: 0 - 11 for peasants, or 0 - 21 for premium users.
-/// To get a string representation of a color's hex use `Color::to_string()`
-#[derive(Debug, Clone)]
+/// To get a color's hex code use `Color::hex()`, `Color::to_string()` gives its name (e.g. "Light Green").
+///
+/// Colors can be parsed from their name, their number, or a hex code.
+/// Hex codes that aren't in Todoist's palette are matched to the nearest color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum Color {
     LightGreen = 0,
@@ -40,6 +41,16 @@ pub enum Color {
     Grey
 }
 
+/// Every color, in order of their number
+const COLORS : [Color; 22] = [
+    Color::LightGreen, Color::LightRed, Color::LightOrange, Color::LightYellow,
+    Color::BlueGrey, Color::LightBrown, Color::Pink, Color::LightGrey,
+    Color::Brown, Color::Yellow, Color::Teal, Color::LightBlue,
+    Color::Purple, Color::Red, Color::Orange, Color::Green,
+    Color::Turquoise, Color::DarkTurquoise, Color::Blue, Color::DarkBlue,
+    Color::Black, Color::Grey,
+];
+
 struct ColorVisitor;
 
 #[derive(Clone,Debug)]
@@ -47,83 +58,141 @@ pub struct UnknownColorErr {
     color : String,
 }
 
-impl<'de> de::Visitor<'de> for ColorVisitor {
-    type Value = Color;
+impl Color {
+    /// Every color, in order of their number
+    pub fn all() -> &'static [Color] {
+        &COLORS
+    }
 
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("an integer between 0 and 21")
+    /// Get the color with the given number, if there is one
+    pub fn from_id(id : u64) -> Option<Color> {
+        COLORS.get(id as usize).cloned()
     }
 
-    fn visit_i8<E>(self, value: i8) -> Result<Color, E>
-        where E: de::Error
-    {
-        if value < 0 || value > 21 {
-            return Err(E::custom(format!("color out of range: {}", value)));
-        }
-        unsafe { Ok(mem::transmute(value)) }
+    /// This color's number
+    pub fn id(&self) -> u8 {
+        *self as u8
     }
 
-    fn visit_i16<E>(self, value: i16) -> Result<Color, E>
-        where E: de::Error
-    {
-        if value < 0 || value > 21 {
-            return Err(E::custom(format!("color out of range: {}", value)));
-        }
-        unsafe { Ok(mem::transmute(value as u8)) }
+    /// True if this color is only available to premium users
+    pub fn is_premium(&self) -> bool {
+        self.id() > 11
     }
 
-    fn visit_i32<E>(self, value: i32) -> Result<Color, E>
-        where E: de::Error
-    {
-        if value < 0 || value > 21 {
-            return Err(E::custom(format!("color out of range: {}", value)));
+    /// This color's hex code, e.g. "#95ef63"
+    pub fn hex(&self) -> &'static str {
+        match self {
+            Color::LightGreen    => "#95ef63",
+            Color::LightRed      => "#ff8581",
+            Color::LightOrange   => "#ffc471",
+            Color::LightYellow   => "#f9ec75",
+            Color::BlueGrey      => "#a8c8e4",
+            Color::LightBrown    => "#d2b8a3",
+            Color::Pink          => "#e2a8e4",
+            Color::LightGrey     => "#cccccc",
+            Color::Brown         => "#fb886e",
+            Color::Yellow        => "#ffcc00",
+            Color::Teal          => "#74e8d3",
+            Color::LightBlue     => "#3bd5fb",
+
+            Color::Purple        => "#dc4fad",
+            Color::Red           => "#ac193d",
+            Color::Orange        => "#d24726",
+            Color::Green         => "#82ba00",
+            Color::Turquoise     => "#03b3b2",
+            Color::DarkTurquoise => "#008299",
+            Color::Blue          => "#5db2ff",
+            Color::DarkBlue      => "#0072c6",
+            Color::Black         => "#000000",
+            Color::Grey          => "#777777",
         }
-        unsafe { Ok(mem::transmute(value as u8)) }
     }
 
-    fn visit_i64<E>(self, value: i64) -> Result<Color, E>
-        where E: de::Error
-    {
-        if value < 0 || value > 21 {
-            return Err(E::custom(format!("color out of range: {}", value)));
+    /// This color's name, e.g. "Light Green"
+    pub fn name(&self) -> &'static str {
+        match self {
+            Color::LightGreen    => "Light Green",
+            Color::LightRed      => "Light Red",
+            Color::LightOrange   => "Light Orange",
+            Color::LightYellow   => "Light Yellow",
+            Color::BlueGrey      => "Blue Grey",
+            Color::LightBrown    => "Light Brown",
+            Color::Pink          => "Pink",
+            Color::LightGrey     => "Light Grey",
+            Color::Brown         => "Brown",
+            Color::Yellow        => "Yellow",
+            Color::Teal          => "Teal",
+            Color::LightBlue     => "Light Blue",
+            Color::Purple        => "Purple",
+            Color::Red           => "Red",
+            Color::Orange        => "Orange",
+            Color::Green         => "Green",
+            Color::Turquoise     => "Turquoise",
+            Color::DarkTurquoise => "Dark Turquoise",
+            Color::Blue          => "Blue",
+            Color::DarkBlue      => "Dark Blue",
+            Color::Black         => "Black",
+            Color::Grey          => "Grey",
         }
-        unsafe { Ok(mem::transmute(value as u8)) }
     }
 
-    fn visit_u8<E>(self, value: u8) -> Result<Color, E>
-        where E: de::Error
-    {
-        if value > 21 {
-            return Err(E::custom(format!("color out of range: {}", value)));
-        }
-        unsafe { Ok(mem::transmute(value)) }
+    /// This color's red, green and blue components
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        parse_hex(self.hex()).unwrap()
     }
 
-    fn visit_u16<E>(self, value: u16) -> Result<Color, E>
+    /// Find the color in Todoist's palette that is closest to an arbitrary color
+    pub fn nearest(r : u8, g : u8, b : u8) -> Color {
+        let distance = |c : &Color| {
+            let (cr, cg, cb) = c.rgb();
+            let (dr, dg, db) = (cr as i32 - r as i32, cg as i32 - g as i32, cb as i32 - b as i32);
+            dr * dr + dg * dg + db * db
+        };
+        *COLORS.iter().min_by_key(|c| distance(c)).unwrap()
+    }
+}
+
+/// parse "#rrggbb", "rrggbb" or "#rgb"
+fn parse_hex(s : &str) -> Option<(u8, u8, u8)> {
+    let hex = s.trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_digit(16)) {
+        return None;
+    }
+
+    let channel = |i : usize, len : usize| u8::from_str_radix(&hex[i * len..(i + 1) * len], 16).ok();
+    match hex.len() {
+        6 => Some((channel(0, 2)?, channel(1, 2)?, channel(2, 2)?)),
+        3 => Some((channel(0, 1)? * 17, channel(1, 1)? * 17, channel(2, 1)? * 17)),
+        _ => None,
+    }
+}
+
+impl<'de> de::Visitor<'de> for ColorVisitor {
+    type Value = Color;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an integer between 0 and 21, a color name or a hex code")
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Color, E>
         where E: de::Error
     {
-        if value > 21 {
+        if value < 0 {
             return Err(E::custom(format!("color out of range: {}", value)));
         }
-        unsafe { Ok(mem::transmute(value as u8)) }
+        self.visit_u64(value as u64)
     }
 
-    fn visit_u32<E>(self, value: u32) -> Result<Color, E>
+    fn visit_u64<E>(self, value: u64) -> Result<Color, E>
         where E: de::Error
     {
-        if value > 21 {
-            return Err(E::custom(format!("color out of range: {}", value)));
-        }
-        unsafe { Ok(mem::transmute(value as u8)) }
+        Color::from_id(value).ok_or_else(|| E::custom(format!("color out of range: {}", value)))
     }
 
-    fn visit_u64<E>(self, value: u64) -> Result<Color, E>
+    fn visit_str<E>(self, value: &str) -> Result<Color, E>
         where E: de::Error
     {
-        if value > 21 {
-            return Err(E::custom(format!("color out of range: {}", value)));
-        }
-        unsafe { Ok(mem::transmute(value as u8)) }
+        value.parse().map_err(E::custom)
     }
 }
 
@@ -139,67 +208,29 @@ impl Error for UnknownColorErr {
     }
 }
 
-impl ToString for Color {
-    fn to_string(&self) -> String {
-        String::from(
-            match self {
-                Color::LightGreen    => "#95ef63",
-                Color::LightRed      => "#ff8581",
-                Color::LightOrange   => "#ffc471",
-                Color::LightYellow   => "#f9ec75",
-                Color::BlueGrey      => "#a8c8e4",
-                Color::LightBrown    => "#d2b8a3",
-                Color::Pink          => "#e2a8e4",
-                Color::LightGrey     => "#cccccc",
-                Color::Brown         => "#fb886e",
-                Color::Yellow        => "#ffcc00",
-                Color::Teal          => "#74e8d3",
-                Color::LightBlue     => "#3bd5fb",
-
-                Color::Purple        => "#dc4fad",
-                Color::Red           => "#ac193d",
-                Color::Orange        => "#d24726",
-                Color::Green         => "#82ba00",
-                Color::Turquoise     => "#03b3b2",
-                Color::DarkTurquoise => "#008299",
-                Color::DarkBlue      => "#5db2ff",
-                Color::Blue          => "#0072c6",
-                Color::Black         => "#000000",
-                Color::Grey          => "#777777",
-            }
-        )
+impl fmt::Display for Color {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
     }
 }
 
 impl FromStr for Color {
     type Err = UnknownColorErr;
 
+    /// Parse a color's name ("Light Green", "lightgreen"), number ("0") or hex code ("#95ef63")
     fn from_str(s : &str) -> Result<Self, Self::Err> {
-        let generic = s.to_uppercase().replace(" ", "");
-        match generic.as_str() {
-            "LIGHTGREEN"  => Ok(Color::LightGreen),
-            "LIGHTRED"    => Ok(Color::LightRed),
-            "LIGHTORANGE" => Ok(Color::LightOrange),
-            "LIGHTYELLOW" => Ok(Color::LightYellow),
-            "BLUEGREY"    => Ok(Color::BlueGrey),
-            "LIGHTBROWN"  => Ok(Color::LightBrown),
-            "PINK"        => Ok(Color::Pink),
-            "LIGHTGREY"   => Ok(Color::LightGrey),
-            "BROWN"       => Ok(Color::Brown),
-            "YELLOW"      => Ok(Color::Yellow),
-            "TEAL"        => Ok(Color::Teal),
-            "LIGHTBLUE"   => Ok(Color::LightBlue),
-            "PURPLE"      => Ok(Color::Purple),
-            "RED"         => Ok(Color::Red),
-            "ORANGE"      => Ok(Color::Orange),
-            "GREEN"       => Ok(Color::Green),
-            "TURQUOISE"   => Ok(Color::Turquoise),
-            "DARKTURQUOISE" => Ok(Color::DarkTurquoise),
-            "DARKBLUE"      => Ok(Color::DarkBlue),
-            "BLUE"          => Ok(Color::Blue),
-            "BLACK"         => Ok(Color::Black),
-            "GREY"          => Ok(Color::Grey),
-            _ => Err(UnknownColorErr { color: s.to_string() })
+        let generic = s.to_uppercase().replace(" ", "").replace("-", "").replace("_", "").replace("GRAY", "GREY");
+        if let Some(color) = COLORS.iter().find(|c| c.name().to_uppercase().replace(" ", "") == generic) {
+            return Ok(*color);
+        }
+
+        if let Some(color) = s.trim().parse().ok().and_then(Color::from_id) {
+            return Ok(color);
+        }
+
+        match parse_hex(s.trim()) {
+            Some((r, g, b)) => Ok(Color::nearest(r, g, b)),
+            None => Err(UnknownColorErr { color: s.to_string() }),
         }
     }
 }
@@ -214,7 +245,7 @@ impl Serialize for Color {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: ser::Serializer
     {
-        serializer.serialize_u8(self.clone() as u8)
+        serializer.serialize_u8(self.id())
     }
 }
 
@@ -223,6 +254,6 @@ impl<'de> Deserialize<'de> for Color {
     fn deserialize<D>(deserializer: D) -> Result<Color, D::Error>
         where D: de::Deserializer<'de>
     {
-        deserializer.deserialize_u8(ColorVisitor)
+        deserializer.deserialize_any(ColorVisitor)
     }
-}
\ No newline at end of file
+}
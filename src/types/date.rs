@@ -1,26 +1,151 @@
 use serde;
 use serde::{Serialize, Deserialize, Serializer, Deserializer};
-use chrono::{DateTime, FixedOffset};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, ParseResult, Utc};
 use chrono::TimeZone;
-use std::default::Default;
+use std::fmt;
+
+use super::serde_helpers;
 
 
 /// FORMAT = [Day Abbreviation] [Day Number (Padded)] [Month Abbreviation] [Year (Padded)] [Time (H:M:S)] [Zone Offset (+xxxx)]
 const FORMAT : &'static str = "%a %d %b %Y %X %z";
 
-#[derive(Debug, Clone)]
+/// A date returned by Todoist, either at a specific time or for a whole day
+///
+/// Todoist sends all-day dates as 23:59:59 in the user's time zone, and marks them separately
+/// (e.g. `Item::all_day`), see `Date::to_all_day`.
+/// All-day dates are stored in the user's zone, so `timestamp`'s local date is always the day the date falls on.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Date {
-    pub timestamp :DateTime<FixedOffset>,
+    pub timestamp : DateTime<FixedOffset>,
+
+    /// true if this date doesn't have a specific time
+    pub all_day : bool,
 }
 
-#[derive(Serialize, Deserialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+
+/// A user's time zone
 pub struct TimeZoneInfo {
-    // TODO: make this struct!
+    /// the time zone's name, e.g. "Europe/Athens"
+    pub timezone : String,
+
+    /// the offset from GMT, e.g. "+03:00"
+    pub gmt_string : String,
+
+    /// the hours portion of the offset from GMT
+    pub hours : i32,
+
+    /// the minutes portion of the offset from GMT
+    pub minutes : i32,
+
+    /// true if daylight saving time is in effect
+    #[serde(deserialize_with = "serde_helpers::bool_or_int")]
+    pub is_dst : bool,
+}
+
+impl Date {
+    /// Create a date at a specific time
+    pub fn from_timestamp(timestamp : DateTime<FixedOffset>) -> Date {
+        Date {
+            timestamp: timestamp,
+            all_day: false,
+        }
+    }
+
+    /// Create an all-day date
+    pub fn from_date(date : NaiveDate, offset : &FixedOffset) -> Date {
+        Date {
+            timestamp: offset.from_local_datetime(&date.and_time(end_of_day())).unwrap(),
+            all_day: true,
+        }
+    }
+
+    /// Parse a date in Todoist's legacy format (`Fri 19 Oct 2018 21:59:59 +0000`), RFC 3339
+    /// (`2018-10-19T17:00:00Z`), a date and time without a zone (treated as UTC) or a plain date (`2018-10-19`)
+    ///
+    /// Dates and times without a zone may also be in the legacy format, e.g. `Fri 19 Oct 2018 21:59:59`.
+    /// Only plain dates are all-day dates, see `Date::to_all_day` for dates Todoist marks as all-day.
+    pub fn parse(s : &str) -> ParseResult<Date> {
+        let utc = FixedOffset::east(0);
+
+        DateTime::parse_from_str(s, FORMAT)
+            .map(Date::from_timestamp)
+            .or_else(|_| DateTime::parse_from_rfc3339(s).map(Date::from_timestamp))
+            .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S")
+                .map(|t| Date::from_timestamp(utc.from_utc_datetime(&t))))
+            .or_else(|_| NaiveDateTime::parse_from_str(s, "%a %d %b %Y %X")
+                .map(|t| Date::from_timestamp(utc.from_utc_datetime(&t))))
+            .or_else(|_| NaiveDate::parse_from_str(s, "%Y-%m-%d").map(|d| Date::from_date(d, &utc)))
+    }
+
+    /// Treat this date as an all-day date in the user's time zone
+    ///
+    /// Todoist sends all-day dates as 23:59:59 in the user's zone, but the current offset may be
+    /// an hour or so off from the one used when the date was set (e.g. across daylight saving time),
+    /// so the day is taken from the nearest noon instead of from the time itself.
+    pub fn to_all_day(&self, offset : &FixedOffset) -> Date {
+        if self.all_day {
+            return self.to_timezone(offset);
+        }
+        let local = self.timestamp.with_timezone(offset).naive_local() - Duration::hours(12);
+        Date::from_date(local.date(), offset)
+    }
+
+    /// The day this date falls on
+    pub fn date(&self) -> NaiveDate {
+        self.timestamp.naive_local().date()
+    }
+
+    /// Convert this date to another time zone
+    ///
+    /// All-day dates keep their day, and end at 23:59:59 in the new zone.
+    pub fn to_timezone(&self, offset : &FixedOffset) -> Date {
+        if self.all_day {
+            Date::from_date(self.date(), offset)
+        } else {
+            Date::from_timestamp(self.timestamp.with_timezone(offset))
+        }
+    }
+}
+
+impl TimeZoneInfo {
+    /// The offset from UTC, or UTC itself if the offset is invalid
+    pub fn offset(&self) -> FixedOffset {
+        let sign = if self.hours < 0 || self.gmt_string.starts_with('-') { -1 } else { 1 };
+        let seconds = sign * (self.hours.abs() * 3600 + self.minutes.abs() * 60);
+        FixedOffset::east_opt(seconds).unwrap_or(FixedOffset::east(0))
+    }
+
+    /// The current time in this time zone
+    pub fn now(&self) -> DateTime<FixedOffset> {
+        Utc::now().with_timezone(&self.offset())
+    }
+}
+
+fn end_of_day() -> NaiveTime {
+    NaiveTime::from_hms(23, 59, 59)
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        if self.all_day {
+            write!(f, "{}", self.timestamp.format("%a %d %b %Y"))
+        } else {
+            write!(f, "{}", self.timestamp.format("%a %d %b %Y %H:%M"))
+        }
+    }
 }
 
 impl Serialize for Date {
     fn serialize<S : Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let s = format!("{}", self.timestamp.format(FORMAT));
+        // all-day dates are written as a plain date, which `Date::parse` reads back as all-day
+        let s = if self.all_day {
+            format!("{}", self.timestamp.format("%Y-%m-%d"))
+        } else {
+            format!("{}", self.timestamp.format(FORMAT))
+        };
         serializer.serialize_str(&s)
     }
 }
@@ -28,16 +153,6 @@ impl Serialize for Date {
 impl<'de> Deserialize<'de> for Date {
     fn deserialize<D : Deserializer<'de>>(deserializer: D) -> Result<Date, D::Error> {
         let s = String::deserialize(deserializer)?;
-        Ok(Date {
-            timestamp: FixedOffset::east(0).datetime_from_str(&s, FORMAT).map_err(serde::de::Error::custom)?
-        })
+        Date::parse(&s).map_err(serde::de::Error::custom)
     }
 }
-
-impl Default for Date {
-    fn default() -> Date {
-        Date {
-            timestamp: FixedOffset::east(0).ymd(1970, 1, 1).and_hms(0, 0, 0)
-        }
-    }
-}
\ No newline at end of file
@@ -2,8 +2,11 @@ use reqwest;
 use serde_json;
 
 use std::fmt;
+use std::io;
 use std::error;
 use CommandErrors;
+use command::ValidationErrors;
+use template::TemplateError;
 
 
 
@@ -13,6 +16,10 @@ pub enum Error {
     Serialize(serde_json::Error),
     ApiError(CommandErrors),
     InvalidApiToken(String),
+    Validation(ValidationErrors),
+    Io(io::Error),
+    Template(TemplateError),
+    Upload(String),
 }
 
 
@@ -34,6 +41,26 @@ impl From<CommandErrors> for Error {
     }
 }
 
+impl From<ValidationErrors> for Error {
+    fn from(e : ValidationErrors) -> Error {
+        Error::Validation(e)
+    }
+}
+
+
+impl From<io::Error> for Error {
+    fn from(e : io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+
+impl From<TemplateError> for Error {
+    fn from(e : TemplateError) -> Error {
+        Error::Template(e)
+    }
+}
+
 
 impl fmt::Display for Error {
     fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
@@ -42,6 +69,10 @@ impl fmt::Display for Error {
             &Error::Serialize(ref e) => write!(f, "{}", e),
             &Error::ApiError(ref e) => write!(f, "{}", e),
             &Error::InvalidApiToken(ref e) => write!(f, "invalid API token \"{}\"", e),
+            &Error::Validation(ref e) => write!(f, "{}", e),
+            &Error::Io(ref e) => write!(f, "{}", e),
+            &Error::Template(ref e) => write!(f, "{}", e),
+            &Error::Upload(ref e) => write!(f, "upload failed: {}", e),
         }
     }
 }
@@ -53,6 +84,10 @@ impl error::Error for Error {
             &Error::Serialize(_) => "serialization failed",
             &Error::ApiError(_) => "api error",
             &Error::InvalidApiToken(_) => "invalid API token",
+            &Error::Validation(_) => "invalid command",
+            &Error::Io(_) => "i/o error",
+            &Error::Template(_) => "invalid template",
+            &Error::Upload(_) => "upload failed",
         }
     }
 }
\ No newline at end of file
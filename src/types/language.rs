@@ -0,0 +1,101 @@
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use std::str::FromStr;
+use std::string::ParseError;
+use std::fmt;
+
+/// A language Todoist can parse date strings in
+///
+/// Languages are sent as 2 character codes (e.g. `en`), codes this crate doesn't know about are kept in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Language {
+    English,
+    Danish,
+    Polish,
+    Chinese,
+    Korean,
+    German,
+    Portuguese,
+    Japanese,
+    Italian,
+    French,
+    Swedish,
+    Russian,
+    Spanish,
+    Dutch,
+
+    /// an unrecognized language code
+    Other(String),
+}
+
+impl Language {
+    /// This language's 2 character code, e.g. "en"
+    pub fn code(&self) -> &str {
+        match self {
+            Language::English    => "en",
+            Language::Danish     => "da",
+            Language::Polish     => "pl",
+            Language::Chinese    => "zh",
+            Language::Korean     => "ko",
+            Language::German     => "de",
+            Language::Portuguese => "pt",
+            Language::Japanese   => "ja",
+            Language::Italian    => "it",
+            Language::French     => "fr",
+            Language::Swedish    => "sv",
+            Language::Russian    => "ru",
+            Language::Spanish    => "es",
+            Language::Dutch      => "nl",
+            Language::Other(ref code) => code,
+        }
+    }
+}
+
+impl Default for Language {
+    fn default() -> Language {
+        Language::English
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Language {
+    type Err = ParseError;
+
+    /// Parse a language code (e.g. "en") or English name (e.g. "english"), unknown values become `Language::Other`
+    fn from_str(s : &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "en" | "english"    => Language::English,
+            "da" | "danish"     => Language::Danish,
+            "pl" | "polish"     => Language::Polish,
+            "zh" | "chinese"    => Language::Chinese,
+            "ko" | "korean"     => Language::Korean,
+            "de" | "german"     => Language::German,
+            "pt" | "portuguese" => Language::Portuguese,
+            "ja" | "japanese"   => Language::Japanese,
+            "it" | "italian"    => Language::Italian,
+            "fr" | "french"     => Language::French,
+            "sv" | "swedish"    => Language::Swedish,
+            "ru" | "russian"    => Language::Russian,
+            "es" | "spanish"    => Language::Spanish,
+            "nl" | "dutch"      => Language::Dutch,
+            _ => Language::Other(s.to_string()),
+        })
+    }
+}
+
+impl Serialize for Language {
+    fn serialize<S : Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Language {
+    fn deserialize<D : Deserializer<'de>>(deserializer: D) -> Result<Language, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(s.parse().unwrap())
+    }
+}
@@ -1,17 +1,49 @@
 mod date;
 mod error;
 mod color;
+mod priority;
+mod language;
+mod patch;
+mod paged;
+pub(crate) mod serde_helpers;
 
 pub use self::date::{Date, TimeZoneInfo};
 pub use self::error::Error;
 pub use self::color::Color;
+pub use self::priority::{Priority, UnknownPriorityErr};
+pub use self::language::Language;
+pub use self::patch::Patch;
+pub use self::paged::{Paged, PageFilter};
 
-/// A 2 character language ID
-/// valid ids: `en`, `da`, `pl`, `zh`, `ko`, `de`, `pt`, `ja`, `it`, `fr`, `sv`, `ru`, `es`, `nl`
-pub type Language = String;
-
-/// A number between 1-4, specifies how important an item is
-pub type Priority = u8;
+use uuid::Uuid;
 
 /// A todoist object ID
 pub type ID = usize;
+
+/// A reference to an object, either by its ID or by the temporary ID of the command creating it
+///
+/// Temporary IDs let a command refer to an object added earlier in the same transaction, see `Transaction::create`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum IdRef {
+    Id(ID),
+    Temp(Uuid),
+}
+
+impl Default for IdRef {
+    fn default() -> IdRef {
+        IdRef::Id(0)
+    }
+}
+
+impl From<ID> for IdRef {
+    fn from(id : ID) -> IdRef {
+        IdRef::Id(id)
+    }
+}
+
+impl From<Uuid> for IdRef {
+    fn from(id : Uuid) -> IdRef {
+        IdRef::Temp(id)
+    }
+}
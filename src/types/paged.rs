@@ -0,0 +1,91 @@
+use chrono::{DateTime, FixedOffset, Utc};
+
+/// The format used by the `since` and `until` parameters
+const FORMAT : &'static str = "%Y-%m-%dT%H:%M";
+
+/// Filters for a request that returns its results a page at a time, e.g. `completed::CompletedFilter`
+///
+/// By default the 30 most recent results are requested.
+/// Use `Paged::next_page` to get the next batch.
+#[derive(Debug, Clone)]
+pub struct Paged<F> {
+    /// the filters specific to the request
+    pub filter : F,
+
+    /// only get results after this time
+    pub since : Option<DateTime<FixedOffset>>,
+
+    /// only get results before this time
+    pub until : Option<DateTime<FixedOffset>>,
+
+    /// the maximum number of results to get
+    pub limit : usize,
+
+    /// the number of results to skip
+    pub offset : usize,
+}
+
+/// Filters that can be requested a page at a time
+pub trait PageFilter {
+    /// The form parameters for these filters
+    fn params(&self) -> Vec<(&'static str, String)>;
+}
+
+impl<F : PageFilter + Default + Clone> Paged<F> {
+    pub fn new() -> Paged<F> {
+        Paged::default()
+    }
+
+    pub fn since(mut self, time : DateTime<FixedOffset>) -> Self {
+        self.since = Some(time);
+        self
+    }
+
+    pub fn until(mut self, time : DateTime<FixedOffset>) -> Self {
+        self.until = Some(time);
+        self
+    }
+
+    pub fn limit(mut self, limit : usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn offset(mut self, offset : usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// The filters for the page after this one
+    pub fn next_page(&self) -> Self {
+        let mut next = self.clone();
+        next.offset += self.limit;
+        next
+    }
+
+    /// The form parameters for these filters, other than the API token
+    pub(crate) fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![("limit", self.limit.to_string()),
+                              ("offset", self.offset.to_string())];
+        params.extend(self.filter.params());
+        if let Some(since) = self.since {
+            params.push(("since", since.with_timezone(&Utc).format(FORMAT).to_string()));
+        }
+        if let Some(until) = self.until {
+            params.push(("until", until.with_timezone(&Utc).format(FORMAT).to_string()));
+        }
+        params
+    }
+}
+
+impl<F : Default> Default for Paged<F> {
+    fn default() -> Paged<F> {
+        Paged {
+            filter: F::default(),
+            since: None,
+            until: None,
+            limit: 30,
+            offset: 0,
+        }
+    }
+}
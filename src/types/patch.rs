@@ -0,0 +1,65 @@
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+
+/// A field in an update command
+///
+/// Only fields that have been set are sent to Todoist, so an update doesn't overwrite
+/// changes made to the other fields elsewhere. `Patch::Clear` sets the field to null.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Patch<T> {
+    /// Leave the field as it is, the field isn't sent
+    Unchanged,
+
+    /// Set the field to a value
+    Set(T),
+
+    /// Remove the field's value, e.g. to remove an item's due date
+    Clear,
+}
+
+impl<T> Patch<T> {
+    /// True if this field won't be sent
+    pub fn is_unchanged(&self) -> bool {
+        match self {
+            Patch::Unchanged => true,
+            _ => false,
+        }
+    }
+
+    /// The value this field will be set to, if any
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Patch::Set(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl<T> Default for Patch<T> {
+    fn default() -> Patch<T> {
+        Patch::Unchanged
+    }
+}
+
+impl<T> From<T> for Patch<T> {
+    fn from(value : T) -> Patch<T> {
+        Patch::Set(value)
+    }
+}
+
+impl<T : Serialize> Serialize for Patch<T> {
+    fn serialize<S : Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Patch::Set(v) => serializer.serialize_some(v),
+            Patch::Unchanged | Patch::Clear => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T : Deserialize<'de>> Deserialize<'de> for Patch<T> {
+    fn deserialize<D : Deserializer<'de>>(deserializer: D) -> Result<Patch<T>, D::Error> {
+        Ok(match Option::<T>::deserialize(deserializer)? {
+            Some(v) => Patch::Set(v),
+            None => Patch::Clear,
+        })
+    }
+}
@@ -0,0 +1,129 @@
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use serde::de;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::str::FromStr;
+use std::fmt;
+
+/// How important an item is
+///
+/// Todoist's UI and API number priorities in opposite directions: the UI's "p1" (the most urgent) is 4 in the API.
+/// The variants here follow the UI, use `Priority::from_api()` and `Priority::api()` to convert.
+///
+/// Priorities are ordered by importance, so `Priority::P1 > Priority::P4`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Priority {
+    /// urgent (4 in the API)
+    P1,
+
+    /// high (3 in the API)
+    P2,
+
+    /// medium (2 in the API)
+    P3,
+
+    /// normal, the default (1 in the API)
+    P4,
+}
+
+#[derive(Clone, Debug)]
+pub struct UnknownPriorityErr {
+    priority : String,
+}
+
+impl Priority {
+    /// Convert a priority number from the API (4 is the most urgent)
+    pub fn from_api(n : u8) -> Option<Priority> {
+        match n {
+            4 => Some(Priority::P1),
+            3 => Some(Priority::P2),
+            2 => Some(Priority::P3),
+            1 => Some(Priority::P4),
+            _ => None,
+        }
+    }
+
+    /// This priority's number in the API (4 is the most urgent)
+    pub fn api(&self) -> u8 {
+        match self {
+            Priority::P1 => 4,
+            Priority::P2 => 3,
+            Priority::P3 => 2,
+            Priority::P4 => 1,
+        }
+    }
+
+    /// This priority's label in the UI, e.g. "p1"
+    pub fn label(&self) -> &'static str {
+        match self {
+            Priority::P1 => "p1",
+            Priority::P2 => "p2",
+            Priority::P3 => "p3",
+            Priority::P4 => "p4",
+        }
+    }
+}
+
+impl Default for Priority {
+    fn default() -> Priority {
+        Priority::P4
+    }
+}
+
+impl Ord for Priority {
+    fn cmp(&self, other : &Priority) -> Ordering {
+        self.api().cmp(&other.api())
+    }
+}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other : &Priority) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for Priority {
+    type Err = UnknownPriorityErr;
+
+    /// Parse a priority as shown in the UI: "p1", "1" or "urgent" are all `Priority::P1`
+    fn from_str(s : &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "p1" | "1" | "urgent"                     => Ok(Priority::P1),
+            "p2" | "2" | "high"                       => Ok(Priority::P2),
+            "p3" | "3" | "medium"                     => Ok(Priority::P3),
+            "p4" | "4" | "normal" | "low" | "none"    => Ok(Priority::P4),
+            _ => Err(UnknownPriorityErr { priority: s.to_string() }),
+        }
+    }
+}
+
+impl fmt::Display for UnknownPriorityErr {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown priority \"{}\", expected p1 - p4", self.priority)
+    }
+}
+
+impl Error for UnknownPriorityErr {
+    fn description(&self) -> &'static str {
+        "unknown priority"
+    }
+}
+
+impl Serialize for Priority {
+    fn serialize<S : Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.api())
+    }
+}
+
+impl<'de> Deserialize<'de> for Priority {
+    fn deserialize<D : Deserializer<'de>>(deserializer: D) -> Result<Priority, D::Error> {
+        let n = u8::deserialize(deserializer)?;
+        Priority::from_api(n).ok_or_else(|| de::Error::custom(format!("priority out of range: {}", n)))
+    }
+}
@@ -0,0 +1,159 @@
+//! Helpers for fields Todoist sends in more than one format
+use serde::{Deserialize, Deserializer, Serializer};
+use serde::de;
+use chrono::NaiveDate;
+use std::fmt;
+
+use super::{ID, Patch};
+
+/// Deserialize an optional number that may be sent as a string, e.g. `"48.8566"` or `48.8566`
+pub fn float_or_string<'de, D : Deserializer<'de>>(deserializer : D) -> Result<Option<f64>, D::Error> {
+    struct FloatVisitor;
+
+    impl<'de> de::Visitor<'de> for FloatVisitor {
+        type Value = Option<f64>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a number, or a string containing a number")
+        }
+
+        fn visit_none<E : de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E : de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D : Deserializer<'de>>(self, deserializer : D) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_any(FloatVisitor)
+        }
+
+        fn visit_f64<E : de::Error>(self, value : f64) -> Result<Self::Value, E> {
+            Ok(Some(value))
+        }
+
+        fn visit_i64<E : de::Error>(self, value : i64) -> Result<Self::Value, E> {
+            Ok(Some(value as f64))
+        }
+
+        fn visit_u64<E : de::Error>(self, value : u64) -> Result<Self::Value, E> {
+            Ok(Some(value as f64))
+        }
+
+        fn visit_str<E : de::Error>(self, value : &str) -> Result<Self::Value, E> {
+            if value.is_empty() {
+                return Ok(None);
+            }
+            value.parse().map(Some).map_err(E::custom)
+        }
+    }
+
+    deserializer.deserialize_option(FloatVisitor)
+}
+
+/// Deserialize a number that may be sent as a string, see `float_or_string`
+pub fn required_float_or_string<'de, D : Deserializer<'de>>(deserializer : D) -> Result<f64, D::Error> {
+    float_or_string(deserializer)?.ok_or_else(|| de::Error::custom("expected a number"))
+}
+
+/// Deserialize an ID that may be sent as a string, e.g. `"2203306141"` or `2203306141`
+pub fn id_or_string<'de, D : Deserializer<'de>>(deserializer : D) -> Result<ID, D::Error> {
+    struct IdVisitor;
+
+    impl<'de> de::Visitor<'de> for IdVisitor {
+        type Value = ID;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an ID, or a string containing an ID")
+        }
+
+        fn visit_u64<E : de::Error>(self, value : u64) -> Result<Self::Value, E> {
+            Ok(value as ID)
+        }
+
+        fn visit_i64<E : de::Error>(self, value : i64) -> Result<Self::Value, E> {
+            if value < 0 {
+                return Err(E::custom(format!("invalid ID: {}", value)));
+            }
+            Ok(value as ID)
+        }
+
+        fn visit_str<E : de::Error>(self, value : &str) -> Result<Self::Value, E> {
+            value.parse().map_err(E::custom)
+        }
+    }
+
+    deserializer.deserialize_any(IdVisitor)
+}
+
+/// Deserialize an optional date (`2018-10-19`), where an empty string means there's no date
+pub fn optional_date<'de, D : Deserializer<'de>>(deserializer : D) -> Result<Option<NaiveDate>, D::Error> {
+    match Option::<String>::deserialize(deserializer)? {
+        Some(ref s) if !s.is_empty() => NaiveDate::parse_from_str(s, "%Y-%m-%d").map(Some).map_err(de::Error::custom),
+        _ => Ok(None),
+    }
+}
+
+/// Deserialize a flag that may be sent as `0`/`1` or `true`/`false`
+pub fn bool_or_int<'de, D : Deserializer<'de>>(deserializer : D) -> Result<bool, D::Error> {
+    struct BoolVisitor;
+
+    impl<'de> de::Visitor<'de> for BoolVisitor {
+        type Value = bool;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a boolean, 0 or 1")
+        }
+
+        fn visit_bool<E : de::Error>(self, value : bool) -> Result<Self::Value, E> {
+            Ok(value)
+        }
+
+        fn visit_u64<E : de::Error>(self, value : u64) -> Result<Self::Value, E> {
+            Ok(value != 0)
+        }
+
+        fn visit_i64<E : de::Error>(self, value : i64) -> Result<Self::Value, E> {
+            Ok(value != 0)
+        }
+
+        fn visit_unit<E : de::Error>(self) -> Result<Self::Value, E> {
+            Ok(false)
+        }
+
+        fn visit_str<E : de::Error>(self, value : &str) -> Result<Self::Value, E> {
+            match value {
+                "1" | "true" => Ok(true),
+                "0" | "false" | "" => Ok(false),
+                _ => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+            }
+        }
+    }
+
+    deserializer.deserialize_any(BoolVisitor)
+}
+
+/// Serialize a flag as `0` or `1`, which is how Todoist expects flags in commands
+pub fn bool_as_int<S : Serializer>(value : &bool, serializer : S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u8(*value as u8)
+}
+
+/// Serialize a flag in an update command as `0` or `1`, see `bool_as_int`
+pub fn patch_bool_as_int<S : Serializer>(value : &Patch<bool>, serializer : S) -> Result<S::Ok, S::Error> {
+    match value {
+        Patch::Set(v) => serializer.serialize_some(&(*v as u8)),
+        Patch::Unchanged | Patch::Clear => serializer.serialize_none(),
+    }
+}
+
+/// Deserialize a flag in an update command, see `bool_or_int`
+pub fn patch_bool_or_int<'de, D : Deserializer<'de>>(deserializer : D) -> Result<Patch<bool>, D::Error> {
+    #[derive(Deserialize)]
+    struct Flag(#[serde(deserialize_with = "bool_or_int")] bool);
+
+    Ok(match Option::<Flag>::deserialize(deserializer)? {
+        Some(Flag(v)) => Patch::Set(v),
+        None => Patch::Clear,
+    })
+}
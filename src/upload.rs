@@ -0,0 +1,71 @@
+use super::{Client, Attachment};
+use super::types::Error;
+
+use reqwest::multipart::{Form, Part};
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Wraps a file, reporting how much of it has been read
+struct ProgressReader<R, F> {
+    inner : R,
+    sent : u64,
+    total : u64,
+    progress : F,
+}
+
+impl<R : Read, F : FnMut(u64, u64)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf : &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.sent += n as u64;
+        (self.progress)(self.sent, self.total);
+        Ok(n)
+    }
+}
+
+impl Client {
+    /// Upload a file, so it can be attached to a note
+    ///
+    /// The returned attachment can be passed to `note::Add::file_attachment`.
+    pub fn upload<P : AsRef<Path>>(&self, path : P) -> Result<Attachment, Error> {
+        self.upload_with_progress(path, |_, _| ())
+    }
+
+    /// Upload a file, calling `progress` with the number of bytes sent and the file's total size as the upload goes on
+    pub fn upload_with_progress<P, F>(&self, path : P, progress : F) -> Result<Attachment, Error>
+        where P : AsRef<Path>,
+              F : FnMut(u64, u64) + Send + 'static
+    {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        let total = file.metadata()?.len();
+        let name = path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "file".to_string());
+
+        let reader = ProgressReader {
+            inner: file,
+            sent: 0,
+            total: total,
+            progress: progress,
+        };
+
+        let form = Form::new()
+            .text("token", self.token.clone())
+            .text("file_name", name.clone())
+            .part("file", Part::reader_with_length(reader, total).file_name(name));
+
+        let res : Attachment = self.client.post("http://todoist.com/api/v7/uploads/add")
+            .multipart(form)
+            .send()?
+            .error_for_status()?
+            .json()?;
+
+        // Todoist answers a failed upload without a URL to the file
+        if res.file_url.is_empty() {
+            return Err(Error::Upload(format!("no URL was returned for \"{}\"", res.file_name)));
+        }
+        Ok(res)
+    }
+}